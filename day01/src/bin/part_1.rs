@@ -1,23 +1,35 @@
+use std::env;
+
+use day01::{Decoder, Mode, Summary};
+
 fn main() {
     let input = include_str!("../../input.txt");
-    println!("{}", solve(input));
+    let mode = if env::args().any(|arg| arg == "--lenient") {
+        Mode::Lenient
+    } else {
+        Mode::Strict
+    };
+    let summary = solve(input, mode);
+    println!("{}", summary.total);
+    eprintln!("{}", summary);
 }
 
-fn solve(input: &str) -> u64 {
-    return input
-        .lines()
-        .map(|line| find_two_digit_number(line) as u64)
-        .sum();
-}
-
-fn find_two_digit_number(line: &str) -> u32 {
-    let digits: Vec<u32> = line.chars().filter_map(|c| c.to_digit(10)).collect();
-    return digits.first().unwrap() * 10 + digits.last().unwrap();
+fn solve(input: &str, mode: Mode) -> Summary {
+    match Decoder::digits_only().summarize(input, mode) {
+        Ok(summary) => summary,
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            panic!("Invalid calibration document");
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::solve;
+    use day01::Mode;
     use indoc::indoc;
 
     #[test]
@@ -29,7 +41,7 @@ mod test {
             treb7uchet
         "};
 
-        let solution = solve(sample);
+        let solution = solve(sample, Mode::Strict).total;
 
         assert_eq!(solution, 142);
     }
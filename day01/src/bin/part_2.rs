@@ -1,47 +1,35 @@
-fn main() {
-    let input = include_str!("../../input.txt");
-    println!("{}", solve(input));
-}
-
-const DIGIT_WORDS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
-
-fn solve(input: &str) -> u64 {
-    return input
-        .lines()
-        .map(|line| find_two_digit_number(line) as u64)
-        .sum();
-}
+use std::env;
 
-fn find_two_digit_number(line: &str) -> u32 {
-    let digits: Vec<u32> = line
-        .char_indices()
-        .filter_map(|(i, _)| find_starting_digit(&line[i..]))
-        .collect();
-    return digits.first().unwrap() * 10 + digits.last().unwrap();
-}
+use day01::{Decoder, Mode, Summary};
 
-fn find_starting_digit(slice: &str) -> Option<u32> {
-    let first_char = slice.chars().next().unwrap();
-    if first_char.is_digit(10) {
-        return first_char.to_digit(10);
-    }
-    return map_text_to_number(slice);
+fn main() {
+    let input = include_str!("../../input.txt");
+    let mode = if env::args().any(|arg| arg == "--lenient") {
+        Mode::Lenient
+    } else {
+        Mode::Strict
+    };
+    let summary = solve(input, mode);
+    println!("{}", summary.total);
+    eprintln!("{}", summary);
 }
 
-fn map_text_to_number(slice: &str) -> Option<u32> {
-    for (i, digit_word) in DIGIT_WORDS.iter().enumerate() {
-        if slice.starts_with(digit_word) {
-            return Some(i as u32 + 1);
+fn solve(input: &str, mode: Mode) -> Summary {
+    match Decoder::english().summarize(input, mode) {
+        Ok(summary) => summary,
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            panic!("Invalid calibration document");
         }
     }
-    return None;
 }
 
 #[cfg(test)]
 mod test {
     use super::solve;
+    use day01::Mode;
     use indoc::indoc;
 
     #[test]
@@ -56,7 +44,7 @@ mod test {
             7pqrstsixteen
         "};
 
-        let solution = solve(sample);
+        let solution = solve(sample, Mode::Strict).total;
 
         assert_eq!(solution, 281);
     }
@@ -0,0 +1,295 @@
+use std::fmt;
+
+use aho_corasick::{AhoCorasick, Anchored, Input, MatchKind, StartKind};
+
+pub const ENGLISH_DIGIT_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+// Finds the first and last digit of a calibration line, written either as a plain digit or as
+// one of the words in its vocabulary. A word can stand for several digits ("twelve" for 12), in
+// which case it gives its leading digit when it comes first and its trailing digit when it comes
+// last.
+//
+// Words may overlap, as in "eightwo": the first digit is the word that starts first and the last
+// digit is the word that starts last, so that line reads as 8 and 2. When several words start at
+// the same position, the one listed first in the vocabulary wins.
+pub struct Decoder {
+    automaton: AhoCorasick,
+    values: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Mode {
+    // Lines without digits are skipped and left out of the total
+    Lenient,
+    // Lines without digits fail the whole document
+    Strict,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CalibrationLine {
+    // 1-based line number in the input
+    pub line: usize,
+    // None when the line has no digits
+    pub value: Option<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub total: u64,
+    pub contributing_lines: usize,
+    // Line numbers of the lines without digits, only ever filled in lenient mode
+    pub skipped_lines: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LineError {
+    pub line: usize,
+}
+
+impl Decoder {
+    pub fn new(vocabulary: &[(&str, u32)]) -> Decoder {
+        let mut patterns: Vec<String> = Vec::new();
+        let mut values: Vec<u32> = Vec::new();
+        for digit in 0..10 {
+            patterns.push(digit.to_string());
+            values.push(digit);
+        }
+        for (word, value) in vocabulary {
+            if word.is_empty() {
+                panic!("Empty word for {} in vocabulary", value);
+            }
+            patterns.push(word.to_string());
+            values.push(*value);
+        }
+
+        // Both start kinds, since the last digit is found with anchored searches from the end
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostFirst)
+            .start_kind(StartKind::Both)
+            .build(patterns)
+            .unwrap();
+        Decoder { automaton, values }
+    }
+
+    pub fn digits_only() -> Decoder {
+        Decoder::new(&[])
+    }
+
+    pub fn english() -> Decoder {
+        Decoder::new(&ENGLISH_DIGIT_WORDS)
+    }
+
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        let first = leading_digit(self.first_value(line)?);
+        let last = self.last_value(line)? % 10;
+        Some(first * 10 + last)
+    }
+
+    pub fn decode(&self, input: &str) -> Vec<CalibrationLine> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| CalibrationLine {
+                line: index + 1,
+                value: self.calibration_value(line),
+            })
+            .collect()
+    }
+
+    // Fails with every line without digits in strict mode
+    pub fn summarize(&self, input: &str, mode: Mode) -> Result<Summary, Vec<LineError>> {
+        let mut summary = Summary {
+            total: 0,
+            contributing_lines: 0,
+            skipped_lines: Vec::new(),
+        };
+        for calibration in self.decode(input) {
+            match calibration.value {
+                Some(value) => {
+                    let _line = overflow::on_line(calibration.line);
+                    summary.total =
+                        overflow::add(summary.total, value as u64, "sum of calibration values");
+                    summary.contributing_lines += 1;
+                }
+                None => summary.skipped_lines.push(calibration.line),
+            }
+        }
+
+        if mode == Mode::Strict && !summary.skipped_lines.is_empty() {
+            return Err(summary
+                .skipped_lines
+                .into_iter()
+                .map(|line| LineError { line })
+                .collect());
+        }
+        Ok(summary)
+    }
+
+    fn first_value(&self, line: &str) -> Option<u32> {
+        self.automaton
+            .find(line)
+            .map(|found| self.values[found.pattern()])
+    }
+
+    // Tries each start position from the end of the line, so the scan stops at the last word
+    // without visiting the rest of the line
+    fn last_value(&self, line: &str) -> Option<u32> {
+        (0..line.len()).rev().find_map(|start| {
+            let input = Input::new(line)
+                .span(start..line.len())
+                .anchored(Anchored::Yes);
+            self.automaton
+                .find(input)
+                .map(|found| self.values[found.pattern()])
+        })
+    }
+}
+
+fn leading_digit(mut value: u32) -> u32 {
+    while value >= 10 {
+        value /= 10;
+    }
+    value
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} lines contributed",
+            self.contributing_lines,
+            self.contributing_lines + self.skipped_lines.len()
+        )?;
+        if !self.skipped_lines.is_empty() {
+            let lines: Vec<String> = self.skipped_lines.iter().map(|l| l.to_string()).collect();
+            write!(f, ", skipped lines without digits: {}", lines.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: no digits", self.line)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CalibrationLine, Decoder, LineError, Mode};
+    use test_case::test_case;
+
+    #[test_case("eightwo", 82)]
+    #[test_case("twone", 21)]
+    #[test_case("oneight", 18)]
+    #[test_case("sevenine", 79)]
+    #[test_case("7pqrstsixteen", 76)]
+    #[test_case("nine", 99)]
+    fn decodes_overlapping_words(line: &str, expected: u32) {
+        let decoder = Decoder::english();
+
+        assert_eq!(decoder.calibration_value(line), Some(expected));
+    }
+
+    #[test]
+    fn ignores_words_without_vocabulary() {
+        let decoder = Decoder::digits_only();
+
+        assert_eq!(decoder.calibration_value("one2three4five"), Some(24));
+        assert_eq!(decoder.calibration_value("onetwothree"), None);
+    }
+
+    #[test]
+    fn decodes_other_languages() {
+        let decoder = Decoder::new(&[
+            ("zero", 0),
+            ("um", 1),
+            ("dois", 2),
+            ("tres", 3),
+            ("oito", 8),
+        ]);
+
+        assert_eq!(decoder.calibration_value("xdoisoitozero"), Some(20));
+        assert_eq!(decoder.calibration_value("tresum7oito"), Some(38));
+    }
+
+    #[test]
+    fn decodes_multi_digit_words() {
+        let decoder = Decoder::new(&[("twelve", 12), ("eighteen", 18), ("eight", 8)]);
+
+        assert_eq!(decoder.calibration_value("twelve"), Some(12));
+        assert_eq!(decoder.calibration_value("eighteen3twelve"), Some(12));
+        assert_eq!(decoder.calibration_value("3eighteen"), Some(38));
+    }
+
+    #[test]
+    fn prefers_earlier_words_at_the_same_position() {
+        let longest_first = Decoder::new(&[("eighteen", 18), ("eight", 8)]);
+        let shortest_first = Decoder::new(&[("eight", 8), ("eighteen", 18)]);
+
+        assert_eq!(longest_first.calibration_value("eighteen"), Some(18));
+        assert_eq!(shortest_first.calibration_value("eighteen"), Some(88));
+    }
+
+    #[test]
+    fn flags_lines_without_digits() {
+        let decoder = Decoder::digits_only();
+
+        assert_eq!(
+            decoder.decode("a1b\nnone\n2x3"),
+            vec![
+                CalibrationLine {
+                    line: 1,
+                    value: Some(11),
+                },
+                CalibrationLine {
+                    line: 2,
+                    value: None,
+                },
+                CalibrationLine {
+                    line: 3,
+                    value: Some(23),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_lines_without_digits_in_lenient_mode() {
+        let decoder = Decoder::english();
+
+        let summary = decoder
+            .summarize("two1nine\nabc\n\nxtwone3four", Mode::Lenient)
+            .unwrap();
+
+        assert_eq!(summary.total, 29 + 24);
+        assert_eq!(summary.contributing_lines, 2);
+        assert_eq!(summary.skipped_lines, vec![2, 3]);
+        assert_eq!(
+            summary.to_string(),
+            "2 of 4 lines contributed, skipped lines without digits: 2, 3"
+        );
+    }
+
+    #[test]
+    fn reports_lines_without_digits_in_strict_mode() {
+        let decoder = Decoder::english();
+
+        let errors = decoder
+            .summarize("two1nine\nabc\n\nxtwone3four", Mode::Strict)
+            .unwrap_err();
+
+        assert_eq!(errors, vec![LineError { line: 2 }, LineError { line: 3 }]);
+        assert_eq!(errors[0].to_string(), "Line 2: no digits");
+    }
+}
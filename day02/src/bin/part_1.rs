@@ -19,8 +19,14 @@ struct CubeSet {
 }
 
 fn solve(input: &str) -> u64 {
-    let games = input.lines().map(Game::parse);
-    return games.filter(Game::is_possible).map(|game| game.id).sum();
+    return overflow::sum_by_line(input, "sum of possible game ids", |line| {
+        let game = Game::parse(line);
+        if game.is_possible() {
+            game.id
+        } else {
+            0
+        }
+    });
 }
 
 impl Game {
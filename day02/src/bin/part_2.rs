@@ -19,11 +19,9 @@ struct CubeSet {
 }
 
 fn solve(input: &str) -> u64 {
-    let games = input.lines().map(Game::parse);
-    return games
-        .map(|game| game.min_cube_set())
-        .map(|cube_set| cube_set.power())
-        .sum();
+    return overflow::sum_by_line(input, "sum of cube set powers", |line| {
+        Game::parse(line).min_cube_set().power()
+    });
 }
 
 impl Game {
@@ -66,7 +64,7 @@ impl CubeSet {
     }
 
     fn power(&self) -> u64 {
-        return self.red * self.green * self.blue;
+        return overflow::product([self.red, self.green, self.blue], "cube set power");
     }
 }
 
@@ -8,6 +8,7 @@ fn solve(input: &str) -> u32 {
 
     let mut sum = 0;
     for (i, line) in matrix.iter().enumerate() {
+        let _line = overflow::on_line(i + 1);
         let mut j = 0;
         while j < line.len() {
             let mut number = 0;
@@ -15,12 +16,17 @@ fn solve(input: &str) -> u32 {
 
             while j < line.len() && matrix[i][j].is_digit(10) {
                 found_adjacent_symbol |= is_adjacent_to_symbol(&matrix, i, j);
-                number = (number * 10) + matrix[i][j].to_digit(10).unwrap();
+                let digit = matrix[i][j].to_digit(10).unwrap();
+                number = overflow::add(
+                    overflow::mul(number, 10, "part number"),
+                    digit,
+                    "part number",
+                );
                 j += 1
             }
 
             if found_adjacent_symbol {
-                sum += number;   
+                sum = overflow::add(sum, number, "sum of part numbers");
             }
             j += 1
         }
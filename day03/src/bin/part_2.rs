@@ -32,6 +32,7 @@ fn solve(input: &str) -> u32 {
     let mut gears: Vec<Point> = Vec::new();
 
     for (i, line) in matrix.iter().enumerate() {
+        let _line = overflow::on_line(i + 1);
         let mut j = 0;
         while j < line.len() {
             let mut k = j;
@@ -40,7 +41,12 @@ fn solve(input: &str) -> u32 {
 
             while k < line.len() && matrix[i][k].is_digit(10) {
                 found_adjacent_gear |= is_adjacent_to_gear(&matrix, (i, k));
-                number = (number * 10) + matrix[i][k].to_digit(10).unwrap();
+                let digit = matrix[i][k].to_digit(10).unwrap();
+                number = overflow::add(
+                    overflow::mul(number, 10, "part number"),
+                    digit,
+                    "part number",
+                );
                 k += 1
             }
 
@@ -62,6 +68,7 @@ fn solve(input: &str) -> u32 {
     let mut gear_ratio = 0;
 
     for gear in gears {
+        let _line = overflow::on_line(gear.0 + 1);
         let gear_adjacent_indexes = adjacent_indexes(&matrix, gear);
         let adjacent_numbers: Vec<u32> = numbers_adjacent_to_gears
             .iter()
@@ -69,7 +76,8 @@ fn solve(input: &str) -> u32 {
             .map(|np| np.number)
             .collect();
         if adjacent_numbers.len() == 2 {
-            gear_ratio += adjacent_numbers[0] * adjacent_numbers[1];
+            let ratio = overflow::mul(adjacent_numbers[0], adjacent_numbers[1], "gear ratio");
+            gear_ratio = overflow::add(gear_ratio, ratio, "sum of gear ratios");
         }
     }
 
@@ -15,7 +15,13 @@ struct Card {
 
 fn solve(input: &str) -> u32 {
     let cards: Vec<Card> = input.lines().map(Card::parse).collect();
-    return cards.iter().map(|c| c.calculate_points()).sum();
+    let mut total_points: u32 = 0;
+    for card in &cards {
+        // Cards are numbered by their line
+        let _line = overflow::on_line(card.id as usize);
+        total_points = overflow::add(total_points, card.calculate_points(), "total points");
+    }
+    return total_points;
 }
 
 impl Card {
@@ -50,7 +56,7 @@ impl Card {
     fn calculate_points(&self) -> u32 {
         match self.count_winning_numbers() {
             0 => 0,
-            count => u32::pow(2, count - 1),
+            count => overflow::pow(2, count - 1, "card points"),
         }
     }
 }
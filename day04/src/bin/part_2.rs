@@ -26,7 +26,13 @@ fn solve(input: &str) -> u32 {
         }
     }
 
-    return cards.iter().map(|c| c.count).sum();
+    let mut total_cards: u32 = 0;
+    for card in &cards {
+        // Cards are numbered by their line
+        let _line = overflow::on_line(card.id as usize);
+        total_cards = overflow::add(total_cards, card.count, "total scratchcards");
+    }
+    return total_cards;
 }
 
 impl Card {
@@ -61,7 +67,8 @@ impl Card {
     }
 
     fn add_copies(&mut self, copies: u32) {
-        self.count += copies;
+        let _line = overflow::on_line(self.id as usize);
+        self.count = overflow::add(self.count, copies, "scratchcard copies");
     }
 }
 
@@ -1,4 +1,4 @@
-use std::str::Lines;
+use std::{iter::Enumerate, str::Lines};
 
 fn main() {
     let input = include_str!("../../input.txt");
@@ -27,6 +27,7 @@ struct ConversionRange {
     source_start: u64,
     destination_start: u64,
     range: u64,
+    line: usize,
 }
 
 fn solve(input: &str) -> u64 {
@@ -41,8 +42,8 @@ fn solve(input: &str) -> u64 {
 
 impl Almanac {
     fn parse(almanac_str: &str) -> Almanac {
-        let mut lines = almanac_str.lines();
-        let seeds_str = lines.next().unwrap();
+        let mut lines = almanac_str.lines().enumerate();
+        let (_, seeds_str) = lines.next().unwrap();
         let seeds = Self::parse_seed_numbers(seeds_str);
         let _blank_line = lines.next();
 
@@ -89,11 +90,11 @@ impl Almanac {
 }
 
 impl ConversionMap {
-    fn parse(map_lines: &mut Lines) -> ConversionMap {
+    fn parse(map_lines: &mut Enumerate<Lines>) -> ConversionMap {
         let _section_title = map_lines.next();
         let ranges: Vec<ConversionRange> = map_lines
-            .take_while(|line| !line.is_empty())
-            .map(ConversionRange::parse)
+            .take_while(|(_, line)| !line.is_empty())
+            .map(|(index, line)| ConversionRange::parse(line, index + 1))
             .collect();
         return ConversionMap { ranges };
     }
@@ -109,7 +110,7 @@ impl ConversionMap {
 }
 
 impl ConversionRange {
-    fn parse(range_str: &str) -> ConversionRange {
+    fn parse(range_str: &str, line: usize) -> ConversionRange {
         let parts: Vec<u64> = range_str
             .split_whitespace()
             .map(|s| s.parse::<u64>().unwrap())
@@ -118,19 +119,24 @@ impl ConversionRange {
             source_start: parts[1],
             destination_start: parts[0],
             range: parts[2],
+            line,
         };
     }
 
     fn applies_to(&self, source: u64) -> bool {
-        return self.source_start <= source && source <= self.source_start + self.range;
+        return self.source_start <= source && source - self.source_start <= self.range;
     }
 
     fn map(&self, source: u64) -> u64 {
         if !self.applies_to(source) {
             panic!("Cannot map {source}");
         }
-        let offset = self.destination_start as i64 - self.source_start as i64;
-        return ((source as i64) + offset) as u64;
+        let _line = overflow::on_line(self.line);
+        return overflow::add(
+            self.destination_start,
+            source - self.source_start,
+            "destination number",
+        );
     }
 }
 
@@ -27,6 +27,7 @@ struct ConversionRange {
     source_start: u64,
     destination_start: u64,
     range: u64,
+    line: usize,
 }
 
 fn solve(input: &str) -> u64 {
@@ -39,8 +40,8 @@ fn solve(input: &str) -> u64 {
 
 impl Almanac {
     fn parse(almanac_str: &str) -> Almanac {
-        let mut lines = almanac_str.lines();
-        let seeds_str = lines.next().unwrap();
+        let mut lines = almanac_str.lines().enumerate();
+        let (_, seeds_str) = lines.next().unwrap();
         let seeds = Self::parse_seed_ranges(seeds_str);
         let _blank_line = lines.next();
 
@@ -70,11 +71,12 @@ impl Almanac {
             .split_whitespace()
             .map(|s| s.parse::<u64>().unwrap())
             .collect();
+        let _line = overflow::on_line(1);
         return numeric_values
             .chunks(2)
             .map(|chunk| Range {
                 start: chunk[0],
-                end: chunk[0] + chunk[1],
+                end: overflow::add(chunk[0], chunk[1], "seed range end"),
             })
             .collect();
     }
@@ -104,11 +106,11 @@ impl Almanac {
 }
 
 impl ConversionMap {
-    fn parse(map_lines: &mut Lines) -> ConversionMap {
+    fn parse(map_lines: &mut iter::Enumerate<Lines>) -> ConversionMap {
         let _section_title = map_lines.next();
         let ranges: Vec<ConversionRange> = map_lines
-            .take_while(|line| !line.is_empty())
-            .map(ConversionRange::parse)
+            .take_while(|(_, line)| !line.is_empty())
+            .map(|(index, line)| ConversionRange::parse(line, index + 1))
             .collect();
         return ConversionMap { ranges };
     }
@@ -124,7 +126,7 @@ impl ConversionMap {
 }
 
 impl ConversionRange {
-    fn parse(range_str: &str) -> ConversionRange {
+    fn parse(range_str: &str, line: usize) -> ConversionRange {
         let parts: Vec<u64> = range_str
             .split_whitespace()
             .map(|s| s.parse::<u64>().unwrap())
@@ -133,20 +135,25 @@ impl ConversionRange {
             source_start: parts[1],
             destination_start: parts[0],
             range: parts[2],
+            line,
         };
     }
 
     fn applies_to_destination(&self, destination: u64) -> bool {
         return self.destination_start <= destination
-            && destination <= self.destination_start + self.range;
+            && destination - self.destination_start <= self.range;
     }
 
     fn map_reverse(&self, destination: u64) -> u64 {
         if !self.applies_to_destination(destination) {
             panic!("Cannot map {destination}");
         }
-        let offset = self.source_start as i64 - self.destination_start as i64;
-        return ((destination as i64) + offset) as u64;
+        let _line = overflow::on_line(self.line);
+        return overflow::add(
+            self.source_start,
+            destination - self.destination_start,
+            "source number",
+        );
     }
 }
 
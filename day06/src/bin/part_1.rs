@@ -42,11 +42,12 @@ impl RaceList {
     }
 
     fn multiply_number_of_ways_to_beat_records(&self) -> u64 {
-        return self
-            .races
-            .iter()
-            .map(|race| race.count_ways_to_beat_record())
-            .product();
+        return overflow::product(
+            self.races
+                .iter()
+                .map(|race| race.count_ways_to_beat_record()),
+            "product of ways to beat records",
+        );
     }
 }
 
@@ -61,7 +62,7 @@ impl Race {
     fn calculate_distance(&self, hold_duration: u32) -> u32 {
         let travel_duration = self.time - hold_duration;
         let speed = hold_duration;
-        return travel_duration * speed;
+        return overflow::mul(travel_duration, speed, "race distance");
     }
 }
 
@@ -42,7 +42,7 @@ impl Race {
     fn calculate_distance(&self, hold_duration: u64) -> u64 {
         let travel_duration = self.time - hold_duration;
         let speed = hold_duration;
-        return travel_duration * speed;
+        return overflow::mul(travel_duration, speed, "race distance");
     }
 }
 
@@ -15,6 +15,7 @@ struct HandBidList {
 struct HandBid {
     hand: Hand,
     bid: u64,
+    line: usize,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
@@ -58,30 +59,35 @@ fn solve(input: &str) -> u64 {
 
 impl HandBidList {
     fn parse(hand_bid_list_str: &str) -> HandBidList {
-        let hands: Vec<HandBid> = hand_bid_list_str.lines().map(HandBid::parse).collect();
+        let hands: Vec<HandBid> = hand_bid_list_str
+            .lines()
+            .enumerate()
+            .map(|(index, line)| HandBid::parse(line, index + 1))
+            .collect();
         return HandBidList { hands };
     }
 
     fn calculate_winnings(&mut self) -> u64 {
         self.hands.sort_by(|h1, h2| h1.hand.cmp(&h2.hand));
 
-        return self
-            .hands
-            .iter()
-            .enumerate()
-            .map(|(i, hand)| hand.bid * (i as u64 + 1))
-            .sum();
+        let mut winnings: u64 = 0;
+        for (i, hand) in self.hands.iter().enumerate() {
+            let _line = overflow::on_line(hand.line);
+            let hand_winnings = overflow::mul(hand.bid, i as u64 + 1, "hand winnings");
+            winnings = overflow::add(winnings, hand_winnings, "total winnings");
+        }
+        return winnings;
     }
 }
 
 impl HandBid {
-    fn parse(hand_bid: &str) -> HandBid {
+    fn parse(hand_bid: &str, line: usize) -> HandBid {
         let parts = hand_bid.split_whitespace().collect::<Vec<&str>>();
         let cards: Vec<Card> = parts[0].chars().map(Card::parse).collect();
         let bid = parts[1].parse::<u64>().unwrap();
 
         let hand = Hand::new(cards);
-        return HandBid { hand, bid };
+        return HandBid { hand, bid, line };
     }
 }
 
@@ -0,0 +1,14 @@
+use std::{env, fs};
+
+use day08::{matcher::Walk, Network};
+
+// Usage: dot <output file> [--ghosts]
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = args.first().expect("Missing output file");
+    let colour_ghosts = args.iter().any(|arg| arg == "--ghosts");
+
+    let input = include_str!("../../input.txt");
+    let network = Network::parse(input);
+    fs::write(path, network.to_dot(&Walk::ghosts(), colour_ghosts)).unwrap();
+}
@@ -0,0 +1,36 @@
+use std::env;
+
+use day08::{
+    matcher::{NodeMatcher, Walk},
+    Network,
+};
+
+// Usage: query <starts> <ends>, with each as exact:<label>, prefix:<text>, suffix:<text> or
+// regex:<pattern>
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let parse_matcher = |arg: Option<&String>| -> NodeMatcher {
+        arg.expect("Missing node matcher")
+            .parse()
+            .expect("Invalid node matcher")
+    };
+    let walk = Walk {
+        starts: parse_matcher(args.first()),
+        ends: parse_matcher(args.get(1)),
+    };
+
+    let input = include_str!("../../input.txt");
+    let network = Network::parse(input);
+
+    for (start, ends) in network.reachable_ends(&walk) {
+        println!(
+            "{start} can reach {} end nodes: {}",
+            ends.len(),
+            ends.join(", ")
+        );
+    }
+    match network.count_steps(&walk) {
+        Ok(steps) => println!("All walks end together after {steps} steps"),
+        Err(error) => println!("Walks never end together: {error:?}"),
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::Write;
+
+use crate::{matcher::Walk, Network};
+
+const GHOST_COLOURS: [&str; 8] = [
+    "blue",
+    "darkorange",
+    "purple",
+    "forestgreen",
+    "deeppink",
+    "brown",
+    "cyan4",
+    "gold3",
+];
+
+impl Network {
+    // Graphviz DOT with L/R edges, start nodes in green and end nodes in red. When colouring
+    // ghosts, every node and edge reachable by a single ghost gets that ghost's colour, and
+    // nodes shared between ghosts are drawn in black.
+    pub fn to_dot(&self, walk: &Walk, colour_ghosts: bool) -> String {
+        let starts = self.matching_nodes(&walk.starts);
+        let mut reached_by: Vec<Vec<usize>> = vec![Vec::new(); self.labels.len()];
+        if colour_ghosts {
+            for (ghost, start) in starts.iter().enumerate() {
+                for node in self.reachable_from(self.ids[*start]) {
+                    reached_by[node as usize].push(ghost);
+                }
+            }
+        }
+        let colour = |node: u32| -> Option<&str> {
+            match reached_by[node as usize].as_slice() {
+                [] => None,
+                [ghost] => Some(GHOST_COLOURS[ghost % GHOST_COLOURS.len()]),
+                _ => Some("black"),
+            }
+        };
+
+        let mut dot = String::from("digraph network {\n");
+        if colour_ghosts {
+            let cycles: Vec<String> = starts
+                .iter()
+                .map(|start| {
+                    let cycle = self.analyse_walk(start, &walk.ends);
+                    format!(
+                        "{start}: tail {}, cycle {}, ends at {:?}, LCM {}",
+                        cycle.tail_length,
+                        cycle.cycle_length,
+                        cycle
+                            .tail_ends
+                            .iter()
+                            .chain(&cycle.cycle_ends)
+                            .collect::<Vec<_>>(),
+                        if cycle.satisfies_lcm_assumption() {
+                            "holds"
+                        } else {
+                            "does not hold"
+                        }
+                    )
+                })
+                .collect();
+            writeln!(dot, "  label=\"{}\";", cycles.join("\\l") + "\\l").unwrap();
+        }
+        writeln!(dot, "  node [style=filled, fillcolor=white];").unwrap();
+
+        for (node, label) in self.labels.iter().enumerate() {
+            let node = node as u32;
+            let mut attributes: Vec<String> = Vec::new();
+            if walk.starts.matches(label) {
+                attributes.push("fillcolor=palegreen".to_owned());
+            } else if walk.ends.matches(label) {
+                attributes.push("fillcolor=salmon".to_owned());
+            }
+            if let Some(colour) = colour(node) {
+                attributes.push(format!("color={colour}, penwidth=2"));
+            }
+            if !attributes.is_empty() {
+                writeln!(dot, "  \"{label}\" [{}];", attributes.join(", ")).unwrap();
+            }
+        }
+
+        for (node, label) in self.labels.iter().enumerate() {
+            let node = node as u32;
+            let edge_colour = colour(node)
+                .map(|colour| format!(", color={colour}"))
+                .unwrap_or_default();
+            let (left, right) = (self.left[node as usize], self.right[node as usize]);
+            let left_label = &self.labels[left as usize];
+            let right_label = &self.labels[right as usize];
+            if left == right {
+                writeln!(
+                    dot,
+                    "  \"{label}\" -> \"{left_label}\" [label=\"L/R\"{edge_colour}];"
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    dot,
+                    "  \"{label}\" -> \"{left_label}\" [label=\"L\"{edge_colour}];"
+                )
+                .unwrap();
+                writeln!(
+                    dot,
+                    "  \"{label}\" -> \"{right_label}\" [label=\"R\"{edge_colour}];"
+                )
+                .unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{matcher::Walk, Network};
+    use indoc::indoc;
+
+    const SAMPLE: &str = indoc! {"
+        LR
+
+        11A = (11B, XXX)
+        11B = (XXX, 11Z)
+        11Z = (11B, XXX)
+        22A = (22Z, 22Z)
+        22Z = (22A, 22A)
+        XXX = (XXX, XXX)
+    "};
+
+    #[test]
+    fn exports_network() {
+        let network = Network::parse(SAMPLE);
+
+        let dot = network.to_dot(&Walk::ghosts(), false);
+
+        assert_eq!(
+            dot,
+            indoc! {r#"
+                digraph network {
+                  node [style=filled, fillcolor=white];
+                  "11A" [fillcolor=palegreen];
+                  "11Z" [fillcolor=salmon];
+                  "22A" [fillcolor=palegreen];
+                  "22Z" [fillcolor=salmon];
+                  "11A" -> "11B" [label="L"];
+                  "11A" -> "XXX" [label="R"];
+                  "11B" -> "XXX" [label="L"];
+                  "11B" -> "11Z" [label="R"];
+                  "11Z" -> "11B" [label="L"];
+                  "11Z" -> "XXX" [label="R"];
+                  "22A" -> "22Z" [label="L/R"];
+                  "22Z" -> "22A" [label="L/R"];
+                  "XXX" -> "XXX" [label="L/R"];
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn colours_ghost_subgraphs() {
+        let network = Network::parse(SAMPLE);
+
+        let dot = network.to_dot(&Walk::ghosts(), true);
+
+        assert!(dot.contains(r#"label="11A: tail 1, cycle 2, ends at [2], LCM holds\l22A: tail 0, cycle 2, ends at [1], LCM does not hold\l";"#));
+        assert!(dot.contains(r#""11B" [color=blue, penwidth=2];"#));
+        assert!(dot.contains(r#""22Z" [fillcolor=salmon, color=darkorange, penwidth=2];"#));
+        assert!(dot.contains(r#""XXX" [color=blue, penwidth=2];"#));
+        assert!(dot.contains(r#""22A" -> "22Z" [label="L/R", color=darkorange];"#));
+    }
+}
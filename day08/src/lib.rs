@@ -0,0 +1,668 @@
+mod dot;
+pub mod matcher;
+
+use std::collections::HashMap;
+
+use matcher::{NodeMatcher, Walk};
+
+// Node labels are interned into dense ids, so that the network is a pair of flat lookup tables
+#[derive(Debug)]
+pub struct Network {
+    instructions: Vec<char>,
+    labels: Vec<String>,
+    ids: HashMap<String, u32>,
+    left: Vec<u32>,
+    right: Vec<u32>,
+    // pass_jumps[k][node] is the node reached after 2^k full passes of the instructions
+    pass_jumps: Vec<Vec<u32>>,
+}
+
+// The walk of a single ghost, split into a tail that is only walked once and a cycle that
+// repeats forever. Steps at which the ghost stands on an end node are given from the start.
+#[derive(Debug, PartialEq)]
+pub struct GhostCycle {
+    pub start: String,
+    pub tail_length: u64,
+    pub cycle_length: u64,
+    pub tail_ends: Vec<u64>,
+    // Ends in the first pass of the cycle; every later pass repeats them `cycle_length` apart
+    pub cycle_ends: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GhostError {
+    NoGhosts,
+    NeverEnds { start: String },
+    NoCommonStep,
+    // Ghosts end on so many steps that neither the congruences nor a scan could settle it
+    TooManyCandidates,
+}
+
+// Bounds on the work spent finding the first step where every ghost ends
+const MAX_CONGRUENCES: usize = 1 << 16;
+const MAX_SCANNED_STEPS: usize = 1 << 24;
+
+impl Network {
+    pub fn parse(input: &str) -> Self {
+        let mut lines = input.lines();
+        let instructions: Vec<char> = lines.next().unwrap().chars().collect();
+        if let Some(instruction) = instructions.iter().find(|c| !['L', 'R'].contains(c)) {
+            panic!("Invalid instruction: {instruction}");
+        }
+        let _blank_line = lines.next();
+
+        let connections: Vec<(String, String, String)> = lines
+            .map(|line| {
+                let parts: Vec<&str> = line.split(" = ").collect();
+                let source = parts[0].to_string();
+                let destinations: Vec<String> = parts[1]
+                    .split(", ")
+                    .map(|dest| {
+                        dest.chars()
+                            .filter(|c| c.is_alphanumeric())
+                            .collect::<String>()
+                    })
+                    .collect();
+                (source, destinations[0].clone(), destinations[1].clone())
+            })
+            .collect();
+
+        let labels: Vec<String> = connections
+            .iter()
+            .map(|(source, _, _)| source.to_owned())
+            .collect();
+        let ids: HashMap<String, u32> = labels
+            .iter()
+            .enumerate()
+            .map(|(id, label)| (label.to_owned(), id as u32))
+            .collect();
+        let id = |label: &String| -> u32 {
+            *ids.get(label)
+                .unwrap_or_else(|| panic!("Unknown node: {label}"))
+        };
+        let left: Vec<u32> = connections.iter().map(|(_, left, _)| id(left)).collect();
+        let right: Vec<u32> = connections.iter().map(|(_, _, right)| id(right)).collect();
+
+        let mut network = Network {
+            instructions,
+            labels,
+            ids,
+            left,
+            right,
+            pass_jumps: Vec::new(),
+        };
+        network.pass_jumps = network.build_pass_jumps();
+        network
+    }
+
+    fn build_pass_jumps(&self) -> Vec<Vec<u32>> {
+        let single_pass: Vec<u32> = (0..self.labels.len() as u32)
+            .map(|node| {
+                self.instructions
+                    .iter()
+                    .fold(node, |node, instruction| self.next(node, *instruction))
+            })
+            .collect();
+
+        let mut pass_jumps: Vec<Vec<u32>> = vec![single_pass];
+        for _ in 1..u64::BITS {
+            let previous = pass_jumps.last().unwrap();
+            let doubled = previous
+                .iter()
+                .map(|node| previous[*node as usize])
+                .collect();
+            pass_jumps.push(doubled);
+        }
+        pass_jumps
+    }
+
+    fn next(&self, node: u32, instruction: char) -> u32 {
+        match instruction {
+            'L' => self.left[node as usize],
+            _ => self.right[node as usize],
+        }
+    }
+
+    fn matching(&self, matcher: &NodeMatcher) -> Vec<bool> {
+        self.labels
+            .iter()
+            .map(|label| matcher.matches(label))
+            .collect()
+    }
+
+    pub fn matching_nodes(&self, matcher: &NodeMatcher) -> Vec<&str> {
+        let mut nodes: Vec<&str> = self
+            .labels
+            .iter()
+            .filter(|label| matcher.matches(label))
+            .map(|label| label.as_str())
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    // Where a walk from a node stands after any number of steps, skipping whole passes of the
+    // instructions in O(log steps)
+    pub fn node_after(&self, start: &str, steps: u64) -> &str {
+        let mut node = self.ids[start];
+        let passes = steps / self.instructions.len() as u64;
+        for (k, jumps) in self.pass_jumps.iter().enumerate() {
+            if passes & (1 << k) != 0 {
+                node = jumps[node as usize];
+            }
+        }
+        let remaining_steps = (steps % self.instructions.len() as u64) as usize;
+        for instruction in self.instructions[..remaining_steps].iter() {
+            node = self.next(node, *instruction);
+        }
+        &self.labels[node as usize]
+    }
+
+    pub fn all_walks_end_at(&self, walk: &Walk, steps: u64) -> bool {
+        self.matching_nodes(&walk.starts)
+            .iter()
+            .all(|start| walk.ends.matches(self.node_after(start, steps)))
+    }
+
+    // Walks from a node until it is back on the same node at the same instruction
+    pub fn analyse_walk(&self, start: &str, ends: &NodeMatcher) -> GhostCycle {
+        let is_end = self.matching(ends);
+        let n_instructions = self.instructions.len();
+        let mut first_seen: Vec<u64> = vec![u64::MAX; self.labels.len() * n_instructions];
+        let mut end_steps: Vec<u64> = Vec::new();
+        let mut node: u32 = self.ids[start];
+        let mut step: u64 = 0;
+
+        loop {
+            let instruction_index = step as usize % n_instructions;
+            let state = node as usize * n_instructions + instruction_index;
+            if first_seen[state] != u64::MAX {
+                let tail_length = first_seen[state];
+                let (tail_ends, cycle_ends) = end_steps.iter().partition(|end| **end < tail_length);
+                return GhostCycle {
+                    start: start.to_owned(),
+                    tail_length,
+                    cycle_length: step - tail_length,
+                    tail_ends,
+                    cycle_ends,
+                };
+            }
+            first_seen[state] = step;
+
+            if is_end[node as usize] {
+                end_steps.push(step);
+            }
+            node = self.next(node, self.instructions[instruction_index]);
+            step += 1;
+        }
+    }
+
+    pub fn analyse_walks(&self, walk: &Walk) -> Vec<GhostCycle> {
+        self.matching_nodes(&walk.starts)
+            .iter()
+            .map(|start| self.analyse_walk(start, &walk.ends))
+            .collect()
+    }
+
+    // Steps until every walk stands on an end node at the same time
+    pub fn count_steps(&self, walk: &Walk) -> Result<u64, GhostError> {
+        let cycles = self.analyse_walks(walk);
+        let steps = first_common_end(&cycles)?;
+        debug_assert!(self.all_walks_end_at(walk, steps));
+        Ok(steps)
+    }
+
+    // For each start node, the end nodes that can be reached from it with any instructions
+    pub fn reachable_ends(&self, walk: &Walk) -> Vec<(&str, Vec<&str>)> {
+        self.matching_nodes(&walk.starts)
+            .into_iter()
+            .map(|start| {
+                let mut ends: Vec<&str> = self
+                    .reachable_from(self.ids[start])
+                    .into_iter()
+                    .map(|node| self.labels[node as usize].as_str())
+                    .filter(|label| walk.ends.matches(label))
+                    .collect();
+                ends.sort();
+                (start, ends)
+            })
+            .collect()
+    }
+
+    fn reachable_from(&self, start: u32) -> Vec<u32> {
+        let mut visited: Vec<bool> = vec![false; self.labels.len()];
+        let mut reachable: Vec<u32> = vec![start];
+        visited[start as usize] = true;
+
+        let mut i = 0;
+        while i < reachable.len() {
+            let node = reachable[i] as usize;
+            for next in [self.left[node], self.right[node]] {
+                if !visited[next as usize] {
+                    visited[next as usize] = true;
+                    reachable.push(next);
+                }
+            }
+            i += 1;
+        }
+        reachable
+    }
+}
+
+impl GhostCycle {
+    pub fn ends_at(&self, step: u64) -> bool {
+        if step < self.tail_length {
+            return self.tail_ends.binary_search(&step).is_ok();
+        }
+        let cycle_offset = (step - self.tail_length) % self.cycle_length;
+        self.cycle_ends
+            .binary_search(&(self.tail_length + cycle_offset))
+            .is_ok()
+    }
+
+    // Taking the LCM of the first ends is only correct when a ghost reaches its one end exactly
+    // once per cycle, and the cycle is as long as the walk to that end
+    pub fn satisfies_lcm_assumption(&self) -> bool {
+        self.tail_ends.is_empty()
+            && self.cycle_ends.len() == 1
+            && self.cycle_ends[0] == self.cycle_length
+    }
+}
+
+pub fn first_common_end(cycles: &[GhostCycle]) -> Result<u64, GhostError> {
+    if cycles.is_empty() {
+        return Err(GhostError::NoGhosts);
+    }
+    if let Some(cycle) = cycles
+        .iter()
+        .find(|cycle| cycle.tail_ends.is_empty() && cycle.cycle_ends.is_empty())
+    {
+        return Err(GhostError::NeverEnds {
+            start: cycle.start.to_owned(),
+        });
+    }
+
+    // Ends within a tail happen only once, so they can be checked one by one
+    let tail_candidate = cycles
+        .iter()
+        .flat_map(|cycle| cycle.tail_ends.iter().copied())
+        .filter(|step| cycles.iter().all(|cycle| cycle.ends_at(*step)))
+        .min();
+
+    let cycle_candidate = match cycle_congruences(cycles) {
+        Some(congruences) => {
+            // Smallest step congruent to a remainder that is past every tail
+            let minimum = cycles.iter().map(|cycle| cycle.tail_length).max().unwrap() as i128;
+            congruences
+                .iter()
+                .map(|(remainder, modulus)| {
+                    let periods = (minimum - remainder).max(0) + modulus - 1;
+                    let step = overflow::mul(periods / modulus, *modulus, "first common step");
+                    overflow::convert(
+                        overflow::add(*remainder, step, "first common step"),
+                        "first common step",
+                    )
+                })
+                .min()
+        }
+        None => scan_cycle_ends(cycles)?,
+    };
+
+    match (tail_candidate, cycle_candidate) {
+        (Some(tail), Some(cycle)) => Ok(tail.min(cycle)),
+        (Some(step), None) | (None, Some(step)) => Ok(step),
+        (None, None) => Err(GhostError::NoCommonStep),
+    }
+}
+
+// Every ghost must be at one of its cycle ends, which is a system of congruences per choice of
+// ends. Gives up once there are too many choices, as when ghosts end on most of their steps.
+fn cycle_congruences(cycles: &[GhostCycle]) -> Option<Vec<(i128, i128)>> {
+    let mut congruences: Vec<(i128, i128)> = vec![(0, 1)];
+    for cycle in cycles {
+        if congruences.len() * cycle.cycle_ends.len() > MAX_CONGRUENCES {
+            return None;
+        }
+        congruences = congruences
+            .iter()
+            .flat_map(|congruence| {
+                cycle.cycle_ends.iter().filter_map(move |end| {
+                    solve_congruences(*congruence, (*end as i128, cycle.cycle_length as i128))
+                })
+            })
+            .collect();
+        // Cycle lengths with common factors lead to the same congruence from several choices
+        congruences.sort_unstable();
+        congruences.dedup();
+    }
+    Some(congruences)
+}
+
+// Checks the cycle ends of the ghost that ends least often against every other ghost, up to
+// the step where all the walks have been through their cycles together
+fn scan_cycle_ends(cycles: &[GhostCycle]) -> Result<Option<u64>, GhostError> {
+    let density = |cycle: &GhostCycle| cycle.cycle_ends.len() as f64 / cycle.cycle_length as f64;
+    let sparsest = cycles
+        .iter()
+        .min_by(|a, b| density(a).total_cmp(&density(b)))
+        .unwrap();
+    let longest_tail = cycles.iter().map(|cycle| cycle.tail_length).max().unwrap();
+    // Past the longest tail, all the walks repeat together every LCM of the cycle lengths
+    let scan_end = cycles
+        .iter()
+        .try_fold(1, |period: u64, cycle| {
+            let gcd = extended_gcd(period as i128, cycle.cycle_length as i128).0 as u64;
+            (period / gcd).checked_mul(cycle.cycle_length)
+        })
+        .and_then(|period| period.checked_add(longest_tail))
+        .unwrap_or(u64::MAX);
+
+    let mut scanned: usize = 0;
+    let mut pass_start: u64 = 0;
+    loop {
+        for end in sparsest.cycle_ends.iter() {
+            let step = overflow::add(*end, pass_start, "scanned step");
+            if step >= scan_end {
+                return Ok(None);
+            }
+            if cycles.iter().all(|cycle| cycle.ends_at(step)) {
+                return Ok(Some(step));
+            }
+            scanned += 1;
+            if scanned == MAX_SCANNED_STEPS {
+                return Err(GhostError::TooManyCandidates);
+            }
+        }
+        pass_start = overflow::add(pass_start, sparsest.cycle_length, "scanned step");
+    }
+}
+
+// Chinese Remainder Theorem for moduli that need not be coprime
+fn solve_congruences(
+    (remainder_a, modulus_a): (i128, i128),
+    (remainder_b, modulus_b): (i128, i128),
+) -> Option<(i128, i128)> {
+    let (gcd, inverse_a, _) = extended_gcd(modulus_a, modulus_b);
+    if (remainder_b - remainder_a) % gcd != 0 {
+        return None;
+    }
+
+    let modulus = overflow::mul(modulus_a / gcd, modulus_b, "combined cycle length");
+    let multiplier = overflow::mul((remainder_b - remainder_a) / gcd, inverse_a, "congruence")
+        .rem_euclid(modulus_b / gcd);
+    let remainder = overflow::add(
+        remainder_a,
+        overflow::mul(modulus_a, multiplier, "congruence"),
+        "congruence",
+    )
+    .rem_euclid(modulus);
+    Some((remainder, modulus))
+}
+
+// Returns gcd(a, b) and x, y such that a * x + b * y = gcd(a, b)
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (gcd, x, y) = extended_gcd(b, a % b);
+    (gcd, y, x - (a / b) * y)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        matcher::{NodeMatcher, Walk},
+        GhostCycle, GhostError, Network,
+    };
+    use indoc::indoc;
+    use test_case::test_case;
+
+    // A ghost walking a loop of `length` nodes with the L instruction, ending where `is_end` says
+    fn ghost_loop(ghost: &str, length: usize, is_end: fn(usize) -> bool) -> String {
+        let label = |k: usize| match k % length {
+            0 => format!("{ghost}A"),
+            k if is_end(k) => format!("{ghost}{k}Z"),
+            k => format!("{ghost}{k}X"),
+        };
+        (0..length)
+            .map(|k| format!("{} = ({}, {})\n", label(k), label(k + 1), label(k + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn analyses_ghost_cycles() {
+        let network = Network::parse(indoc! {"
+            LR
+
+            11A = (11B, XXX)
+            11B = (XXX, 11Z)
+            11Z = (11B, XXX)
+            XXX = (XXX, XXX)
+        "});
+
+        let cycle = network.analyse_walk("11A", &Walk::ghosts().ends);
+
+        assert_eq!(
+            cycle,
+            GhostCycle {
+                start: "11A".to_owned(),
+                tail_length: 1,
+                cycle_length: 2,
+                tail_ends: vec![],
+                cycle_ends: vec![2],
+            }
+        );
+        assert!(cycle.satisfies_lcm_assumption());
+    }
+
+    #[test]
+    fn solves_ghosts_with_offset_cycles() {
+        let network = Network::parse(indoc! {"
+            L
+
+            11A = (11B, 11B)
+            11B = (11Z, 11Z)
+            11Z = (11B, 11B)
+            22A = (22B, 22B)
+            22B = (22C, 22C)
+            22C = (22D, 22D)
+            22D = (22E, 22E)
+            22E = (22Z, 22Z)
+            22Z = (22F, 22F)
+            22F = (22G, 22G)
+            22G = (22Z, 22Z)
+        "});
+
+        let steps = network.count_steps(&Walk::ghosts());
+
+        assert!(!network
+            .analyse_walk("22A", &Walk::ghosts().ends)
+            .satisfies_lcm_assumption());
+        assert_eq!(steps, Ok(8));
+    }
+
+    #[test]
+    fn solves_ghosts_with_several_ends_per_cycle() {
+        let network = Network::parse(indoc! {"
+            L
+
+            11A = (11B, 11B)
+            11B = (11Z, 11Z)
+            11Z = (12Z, 12Z)
+            12Z = (11C, 11C)
+            11C = (11D, 11D)
+            11D = (11A, 11A)
+            22A = (22Z, 22Z)
+            22Z = (22B, 22B)
+            22B = (23Z, 23Z)
+            23Z = (22A, 22A)
+            33A = (33B, 33B)
+            33B = (33C, 33C)
+            33C = (33D, 33D)
+            33D = (33Z, 33Z)
+            33Z = (33E, 33E)
+            33E = (34Z, 34Z)
+            34Z = (33F, 33F)
+            33F = (33G, 33G)
+            33G = (33A, 33A)
+        "});
+
+        let cycles = network.analyse_walks(&Walk::ghosts());
+        let steps = network.count_steps(&Walk::ghosts());
+
+        assert_eq!(
+            cycles
+                .iter()
+                .map(|cycle| cycle.cycle_ends.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![2, 3], vec![1, 3], vec![4, 6]]
+        );
+        assert_eq!(steps, Ok(15));
+    }
+
+    // Hundreds of ends per ghost are too many choices of congruences, so these are scanned
+    #[test_case(601, 599, Ok(600); "meeting")]
+    #[test_case(600, 602, Err(GhostError::NoCommonStep); "never meeting")]
+    fn scans_ghosts_ending_on_most_steps(
+        even_length: usize,
+        odd_length: usize,
+        expected: Result<u64, GhostError>,
+    ) {
+        let input = format!(
+            "L\n\n{}{}",
+            ghost_loop("E", even_length, |k| k % 2 == 0),
+            ghost_loop("O", odd_length, |k| k % 2 == 1)
+        );
+        let network = Network::parse(&input);
+
+        let steps = network.count_steps(&Walk::ghosts());
+
+        assert_eq!(steps, expected);
+        if let Ok(steps) = steps {
+            let first = (0..).find(|step| network.all_walks_end_at(&Walk::ghosts(), *step));
+            assert_eq!(first, Some(steps));
+        }
+    }
+
+    #[test]
+    fn reports_ghosts_that_never_meet() {
+        let network = Network::parse(indoc! {"
+            L
+
+            11A = (11Z, 11Z)
+            11Z = (11B, 11B)
+            11B = (11Z, 11Z)
+            22A = (22B, 22B)
+            22B = (22Z, 22Z)
+            22Z = (22C, 22C)
+            22C = (22Z, 22Z)
+        "});
+
+        let steps = network.count_steps(&Walk::ghosts());
+
+        assert_eq!(steps, Err(GhostError::NoCommonStep));
+    }
+
+    #[test]
+    fn reports_ghosts_that_never_end() {
+        let network = Network::parse(indoc! {"
+            L
+
+            11A = (11B, 11B)
+            11B = (11A, 11A)
+        "});
+
+        let steps = network.count_steps(&Walk::ghosts());
+
+        assert_eq!(
+            steps,
+            Err(GhostError::NeverEnds {
+                start: "11A".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn jumps_over_whole_passes() {
+        let network = Network::parse(indoc! {"
+            LLR
+
+            AAA = (BBB, CCC)
+            BBB = (DDD, AAA)
+            CCC = (AAA, DDD)
+            DDD = (CCC, BBB)
+        "});
+
+        let mut node = "AAA";
+        for steps in 0..100 {
+            assert_eq!(network.node_after("AAA", steps), node);
+            let instruction = ["L", "L", "R"][steps as usize % 3];
+            let pair = match node {
+                "AAA" => ("BBB", "CCC"),
+                "BBB" => ("DDD", "AAA"),
+                "CCC" => ("AAA", "DDD"),
+                _ => ("CCC", "BBB"),
+            };
+            node = if instruction == "L" { pair.0 } else { pair.1 };
+        }
+    }
+
+    #[test]
+    fn verifies_ends_trillions_of_steps_away() {
+        let network = Network::parse(indoc! {"
+            L
+
+            11A = (11B, 11B)
+            11B = (11Z, 11Z)
+            11Z = (11B, 11B)
+            22A = (22B, 22B)
+            22B = (22C, 22C)
+            22C = (22Z, 22Z)
+            22Z = (22B, 22B)
+        "});
+
+        assert!(network.all_walks_end_at(&Walk::ghosts(), 6_000_000_000_000));
+        assert!(!network.all_walks_end_at(&Walk::ghosts(), 6_000_000_000_001));
+        assert!(!network.all_walks_end_at(&Walk::ghosts(), 6_000_000_000_002));
+    }
+
+    #[test]
+    fn walks_between_exact_nodes() {
+        let network = Network::parse(indoc! {"
+            LLR
+
+            AAA = (BBB, BBB)
+            BBB = (AAA, ZZZ)
+            ZZZ = (ZZZ, ZZZ)
+        "});
+
+        let steps = network.count_steps(&Walk::single());
+
+        assert_eq!(steps, Ok(6));
+    }
+
+    #[test]
+    fn finds_reachable_ends() {
+        let network = Network::parse(indoc! {"
+            LR
+
+            11A = (11B, XXX)
+            11B = (XXX, 11Z)
+            11Z = (11B, XXX)
+            22A = (22Z, 22Z)
+            22Z = (22A, 22A)
+            XXX = (XXX, XXX)
+        "});
+        let walk = Walk {
+            starts: NodeMatcher::Regex("^[12]{2}A$".parse().unwrap()),
+            ends: NodeMatcher::Prefix("11".to_owned()),
+        };
+
+        let reachable = network.reachable_ends(&walk);
+
+        assert_eq!(
+            reachable,
+            vec![("11A", vec!["11A", "11B", "11Z"]), ("22A", vec![])]
+        );
+    }
+}
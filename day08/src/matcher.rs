@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub enum NodeMatcher {
+    Exact(String),
+    Prefix(String),
+    Suffix(String),
+    Regex(Regex),
+}
+
+// Where walks start and which nodes end them
+#[derive(Debug, Clone)]
+pub struct Walk {
+    pub starts: NodeMatcher,
+    pub ends: NodeMatcher,
+}
+
+impl NodeMatcher {
+    pub fn matches(&self, label: &str) -> bool {
+        match self {
+            NodeMatcher::Exact(exact) => label == exact,
+            NodeMatcher::Prefix(prefix) => label.starts_with(prefix.as_str()),
+            NodeMatcher::Suffix(suffix) => label.ends_with(suffix.as_str()),
+            NodeMatcher::Regex(regex) => regex.is_match(label),
+        }
+    }
+}
+
+impl Walk {
+    // Part 1: from AAA to ZZZ
+    pub fn single() -> Walk {
+        Walk {
+            starts: NodeMatcher::Exact("AAA".to_owned()),
+            ends: NodeMatcher::Exact("ZZZ".to_owned()),
+        }
+    }
+
+    // Part 2: from every node ending in A to nodes ending in Z
+    pub fn ghosts() -> Walk {
+        Walk {
+            starts: NodeMatcher::Suffix("A".to_owned()),
+            ends: NodeMatcher::Suffix("Z".to_owned()),
+        }
+    }
+}
+
+// Parses `exact:<label>`, `prefix:<text>`, `suffix:<text>` or `regex:<pattern>`
+impl FromStr for NodeMatcher {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s.split_once(':').ok_or(())?;
+        match kind {
+            "exact" => Ok(NodeMatcher::Exact(value.to_owned())),
+            "prefix" => Ok(NodeMatcher::Prefix(value.to_owned())),
+            "suffix" => Ok(NodeMatcher::Suffix(value.to_owned())),
+            "regex" => Regex::new(value).map(NodeMatcher::Regex).map_err(|_| ()),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NodeMatcher;
+    use test_case::test_case;
+
+    #[test_case("exact:AAA", "AAA", true)]
+    #[test_case("exact:AAA", "AAAA", false)]
+    #[test_case("prefix:11", "11A", true)]
+    #[test_case("prefix:11", "211", false)]
+    #[test_case("suffix:Z", "11Z", true)]
+    #[test_case("suffix:Z", "Z11", false)]
+    #[test_case("regex:^[A-C]{2}Z$", "BCZ", true)]
+    #[test_case("regex:^[A-C]{2}Z$", "BDZ", false)]
+    fn matches_labels(matcher: &str, label: &str, expected: bool) {
+        let matcher: NodeMatcher = matcher.parse().unwrap();
+
+        assert_eq!(matcher.matches(label), expected);
+    }
+
+    #[test_case("AAA")]
+    #[test_case("glob:*A")]
+    #[test_case("regex:(")]
+    fn rejects_invalid_matchers(matcher: &str) {
+        assert!(matcher.parse::<NodeMatcher>().is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use std::{fmt, str::FromStr};
+
+use num::{BigInt, BigRational, One, Zero};
+
+#[derive(Debug)]
+pub struct Oasis {
+    pub histories: Vec<ValueHistory>,
+}
+
+// A history seen as the polynomial through its values, where x is the offset from the first
+// value. The polynomial is kept in Newton form: differences[j] is the first value of the j-th
+// difference sequence, so that f(x) = sum of differences[j] * C(x, j).
+#[derive(Debug)]
+pub struct ValueHistory {
+    values: Vec<BigInt>,
+    differences: Vec<BigInt>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum HistoryError {
+    Empty,
+    InvalidValue(String),
+    // The differences never reach a row of all zeros before only one value is left. Single
+    // nonzero values and two values that differ are rejected too, as their next value is
+    // a guess rather than something the history pins down.
+    NotPolynomial,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LineError {
+    // 1-based line number in the input
+    pub line: usize,
+    pub error: HistoryError,
+}
+
+impl ValueHistory {
+    pub fn new(values: Vec<BigInt>) -> Result<ValueHistory, HistoryError> {
+        if values.is_empty() {
+            return Err(HistoryError::Empty);
+        }
+
+        let mut differences: Vec<BigInt> = Vec::new();
+        let mut sequence = values.to_owned();
+        while !sequence.iter().all(|v| v.is_zero()) {
+            if sequence.len() == 1 {
+                return Err(HistoryError::NotPolynomial);
+            }
+            differences.push(sequence[0].clone());
+            sequence = sequence
+                .windows(2)
+                .map(|pair| &pair[1] - &pair[0])
+                .collect();
+        }
+
+        Ok(ValueHistory {
+            values,
+            differences,
+        })
+    }
+
+    pub fn extrapolate_next(&self) -> BigInt {
+        self.value_at(&BigInt::from(self.values.len()))
+    }
+
+    pub fn extrapolate_previous(&self) -> BigInt {
+        self.value_at(&BigInt::from(-1))
+    }
+
+    // Value at any offset from the first value, negative offsets going back in time
+    pub fn value_at(&self, x: &BigInt) -> BigInt {
+        let mut value = BigInt::zero();
+        let mut binomial = BigInt::one();
+        for (j, difference) in self.differences.iter().enumerate() {
+            value += difference * &binomial;
+            // C(x, j + 1) = C(x, j) * (x - j) / (j + 1), which always divides exactly
+            binomial = binomial * (x - j) / (j + 1);
+        }
+        value
+    }
+
+    pub fn degree(&self) -> usize {
+        self.differences.len().saturating_sub(1)
+    }
+
+    // Coefficients of the polynomial, lowest power of x first
+    pub fn coefficients(&self) -> Vec<BigRational> {
+        let mut coefficients: Vec<BigRational> = vec![BigRational::zero(); self.degree() + 1];
+        // Coefficients of x * (x - 1) * ... * (x - j + 1), lowest power first
+        let mut falling_factorial: Vec<BigInt> = vec![BigInt::one()];
+        let mut factorial = BigInt::one();
+
+        for (j, difference) in self.differences.iter().enumerate() {
+            let scale = BigRational::new(difference.clone(), factorial.clone());
+            for (power, coefficient) in falling_factorial.iter().enumerate() {
+                coefficients[power] += &scale * BigRational::from_integer(coefficient.clone());
+            }
+
+            let mut next: Vec<BigInt> = vec![BigInt::zero(); falling_factorial.len() + 1];
+            for (power, coefficient) in falling_factorial.iter().enumerate() {
+                next[power + 1] += coefficient;
+                next[power] -= coefficient * BigInt::from(j);
+            }
+            falling_factorial = next;
+            factorial *= BigInt::from(j + 1);
+        }
+
+        coefficients
+    }
+}
+
+// Fails with every line that is not a polynomial history
+impl FromStr for Oasis {
+    type Err = Vec<LineError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut histories: Vec<ValueHistory> = Vec::new();
+        let mut errors: Vec<LineError> = Vec::new();
+        for (index, line) in s.lines().enumerate() {
+            match line.parse::<ValueHistory>() {
+                Ok(history) => histories.push(history),
+                Err(error) => errors.push(LineError {
+                    line: index + 1,
+                    error,
+                }),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Oasis { histories })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl FromStr for ValueHistory {
+    type Err = HistoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Vec<BigInt> = s
+            .split_whitespace()
+            .map(|s| {
+                s.parse::<BigInt>()
+                    .map_err(|_| HistoryError::InvalidValue(s.to_owned()))
+            })
+            .collect::<Result<_, _>>()?;
+        ValueHistory::new(values)
+    }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            HistoryError::Empty => write!(f, "Line {}: empty history", self.line),
+            HistoryError::InvalidValue(value) => {
+                write!(f, "Line {}: invalid value '{value}'", self.line)
+            }
+            HistoryError::NotPolynomial => {
+                write!(f, "Line {}: history is not a polynomial", self.line)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HistoryError, LineError, Oasis, ValueHistory};
+    use num::{BigInt, BigRational};
+    use test_case::test_case;
+
+    fn rationals(values: &[(i64, i64)]) -> Vec<BigRational> {
+        values
+            .iter()
+            .map(|(numerator, denominator)| {
+                BigRational::new((*numerator).into(), (*denominator).into())
+            })
+            .collect()
+    }
+
+    #[test_case("0 3 6 9 12 15", 6, 18)]
+    #[test_case("10 13 16 21 30 45", 6, 68)]
+    #[test_case("10 13 16 21 30 45", -1, 5)]
+    #[test_case("10 13 16 21 30 45", -3, -19)]
+    #[test_case("10 13 16 21 30 45", 2, 16)]
+    #[test_case("1 3 6 10 15 21", 100, 5151)]
+    #[test_case("1 3 6 10 15 21", -100, 4851)]
+    fn extrapolates_to_any_offset(history: &str, x: i64, expected: i64) {
+        let history: ValueHistory = history.parse().unwrap();
+
+        assert_eq!(history.value_at(&x.into()), expected.into());
+    }
+
+    #[test]
+    fn finds_polynomial() {
+        let history: ValueHistory = "1 3 6 10 15 21".parse().unwrap();
+
+        assert_eq!(history.degree(), 2);
+        assert_eq!(history.coefficients(), rationals(&[(1, 1), (3, 2), (1, 2)]));
+    }
+
+    #[test]
+    fn finds_cubic_polynomial() {
+        let history: ValueHistory = "10 13 16 21 30 45".parse().unwrap();
+
+        assert_eq!(history.degree(), 3);
+        assert_eq!(
+            history.coefficients(),
+            rationals(&[(10, 1), (11, 3), (-1, 1), (1, 3)])
+        );
+    }
+
+    #[test_case("1 2 4 8 16 32")]
+    #[test_case("5")]
+    #[test_case("0 3")]
+    fn rejects_non_polynomial_histories(history: &str) {
+        let error = history.parse::<ValueHistory>().unwrap_err();
+
+        assert_eq!(error, HistoryError::NotPolynomial);
+    }
+
+    #[test_case("0", 0)]
+    #[test_case("7 7", 7)]
+    #[test_case("0 3 6", 9)]
+    fn accepts_short_histories_that_reach_zeros(history: &str, expected: i64) {
+        let history: ValueHistory = history.parse().unwrap();
+
+        assert_eq!(history.extrapolate_next(), expected.into());
+    }
+
+    #[test]
+    fn reports_every_invalid_line() {
+        let input = "0 3 6 9\n1 1 2 3 5 8\n\n1 x 3\n0 0 0\n";
+
+        let errors = input.parse::<Oasis>().unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                LineError {
+                    line: 2,
+                    error: HistoryError::NotPolynomial,
+                },
+                LineError {
+                    line: 3,
+                    error: HistoryError::Empty,
+                },
+                LineError {
+                    line: 4,
+                    error: HistoryError::InvalidValue("x".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extrapolates_beyond_i64() {
+        let history: ValueHistory = "0 9223372036854775807 18446744073709551614"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            history.extrapolate_next(),
+            "27670116110564327421".parse::<BigInt>().unwrap()
+        );
+    }
+}
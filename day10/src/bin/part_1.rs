@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use rectilinear::RectilinearPolygon;
+
 fn main() {
     let input = include_str!("../../input.txt");
     println!("{}", solve(input));
@@ -48,10 +50,15 @@ enum Direction {
     W,
 }
 
-fn solve(input: &str) -> u64 {
+fn solve(input: &str) -> u128 {
     let maze: Maze = input.parse().unwrap();
     let loop_path = maze.find_loop();
-    loop_path.len() as u64 / 2
+    let vertices: Vec<(i128, i128)> = loop_path
+        .iter()
+        .map(|(i, j)| (*i as i128, *j as i128))
+        .collect();
+    // The farthest tile is halfway around the loop
+    RectilinearPolygon::new(vertices).perimeter() / 2
 }
 
 impl Maze {
@@ -1,6 +1,7 @@
-use std::{collections::HashSet, str::FromStr};
+use std::str::FromStr;
+
+use rectilinear::RectilinearPolygon;
 
-// FIXME Test cases pass, but actual answer is incorrect: 464, too high
 fn main() {
     let input = include_str!("../../input.txt");
     println!("{}", solve(input));
@@ -49,7 +50,7 @@ enum Direction {
     W,
 }
 
-fn solve(input: &str) -> u64 {
+fn solve(input: &str) -> u128 {
     let maze: Maze = input.parse().unwrap();
     maze.count_tiles_inside_loop()
 }
@@ -67,51 +68,15 @@ impl Maze {
         }
     }
 
-    fn count_tiles_inside_loop(&self) -> u64 {
-        let (start_tile, loop_path) = self.find_loop();
-        let loop_set: HashSet<&Point> = loop_path.iter().collect();
-
-        let mut count: u64 = 0;
-
-        for (i, row) in self.tiles.iter().enumerate() {
-            let mut boundary_hits: u32 = 0;
-            let mut last_boundary: Option<&PipeType> = None;
-
-            for (j, tile) in row.iter().enumerate() {
-                if loop_set.contains(&(i, j)) {
-                    let pipe: &PipeType = match tile {
-                        TileType::Pipe(pipe) => pipe,
-                        TileType::Start => &start_tile,
-                        _ => panic!("Tile in loop path must be a pipe"),
-                    };
-
-                    if let PipeType::EW = pipe {
-                        continue;
-                    }
-
-                    boundary_hits += 1;
-
-                    if let PipeType::NW = pipe {
-                        if let Some(PipeType::SE) = last_boundary {
-                            boundary_hits -= 1;
-                        }
-                    }
-                    if let PipeType::SW = pipe {
-                        if let Some(PipeType::NE) = last_boundary {
-                            boundary_hits -= 1;
-                        }
-                    }
-
-                    last_boundary = Some(pipe);
-                } else {
-                    if boundary_hits % 2 == 1 {
-                        count += 1;
-                    }
-                }
-            }
-        }
-
-        count
+    // The loop runs through the centre of its tiles, so the tiles inside it are the lattice
+    // points strictly inside the polygon it draws
+    fn count_tiles_inside_loop(&self) -> u128 {
+        let loop_path = self.find_loop();
+        let vertices: Vec<(i128, i128)> = loop_path
+            .iter()
+            .map(|(i, j)| (*i as i128, *j as i128))
+            .collect();
+        RectilinearPolygon::new(vertices).interior_points()
     }
 
     fn find_start(tiles: &Vec<Vec<TileType>>) -> Point {
@@ -125,11 +90,11 @@ impl Maze {
         panic!("Start not found");
     }
 
-    fn find_loop(&self) -> (PipeType, Vec<Point>) {
+    fn find_loop(&self) -> Vec<Point> {
         for start_tile in PipeType::VALUES {
             for start_direction in Direction::VALUES {
                 if let Ok(path) = self.find_loop_given_start(start_tile, start_direction) {
-                    return (start_tile, path);
+                    return path;
                 }
             }
         }
@@ -342,4 +307,21 @@ mod test {
 
         assert_eq!(solution, 10);
     }
+
+    // The previous row scan read S as the first pipe that closed a loop, here | instead of L,
+    // so it missed the three tiles to the right of S and counted 8
+    #[test]
+    fn counts_tiles_beside_a_corner_start() {
+        let sample = indoc! {"
+            F-----7
+            |.....|
+            S-7...|
+            ..|...|
+            ..L---J
+        "};
+
+        let solution = solve(sample);
+
+        assert_eq!(solution, 11);
+    }
 }
@@ -0,0 +1,15 @@
+use std::{env, fs};
+
+use day11::{Expansion, Image};
+
+// Usage: export <output file> [expansion factor]
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = args.first().expect("Missing output file");
+    let expansion_factor: u64 = args.get(1).map_or(2, |factor| factor.parse().unwrap());
+
+    let input = include_str!("../../input.txt");
+    let mut image: Image = input.parse().unwrap();
+    image.set_expansion(Expansion::Uniform(expansion_factor));
+    fs::write(path, image.to_csv()).unwrap();
+}
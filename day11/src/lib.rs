@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+mod query;
+
+pub type Point = (usize, usize);
+
+#[derive(Debug)]
+pub struct Image {
+    pixels: Vec<Vec<char>>,
+    empty_rows: Vec<bool>,
+    empty_columns: Vec<bool>,
+    pub row_expansion: Expansion,
+    pub column_expansion: Expansion,
+}
+
+// How many lines every empty line becomes once the universe expands
+#[derive(Debug, Clone)]
+pub enum Expansion {
+    Uniform(u64),
+    // Width of each empty line given the length of the run of consecutive empty lines it is in
+    RunLength(fn(usize) -> u64),
+    // Width of each empty line by its index, where empty lines past the table don't expand
+    Weights(Vec<u64>),
+}
+
+impl Expansion {
+    // Width of every line, empty or not, after expansion
+    fn widths(&self, empty_lines: &[bool]) -> Vec<u64> {
+        let mut widths: Vec<u64> = vec![1; empty_lines.len()];
+        let mut run_start: usize = 0;
+        while run_start < empty_lines.len() {
+            if !empty_lines[run_start] {
+                run_start += 1;
+                continue;
+            }
+            let run_length = empty_lines[run_start..]
+                .iter()
+                .take_while(|is_empty| **is_empty)
+                .count();
+            for (index, width) in widths[run_start..run_start + run_length]
+                .iter_mut()
+                .enumerate()
+            {
+                *width = match self {
+                    Expansion::Uniform(factor) => *factor,
+                    Expansion::RunLength(width_in_run) => width_in_run(run_length),
+                    Expansion::Weights(weights) => {
+                        weights.get(run_start + index).copied().unwrap_or(1)
+                    }
+                };
+            }
+            run_start += run_length;
+        }
+        widths
+    }
+}
+
+impl Image {
+    pub fn new(pixels: Vec<Vec<char>>) -> Image {
+        let empty_rows: Vec<bool> = pixels
+            .iter()
+            .map(|row| row.iter().all(|c| *c == '.'))
+            .collect();
+        let width = pixels.first().map_or(0, |row| row.len());
+        let empty_columns: Vec<bool> = (0..width)
+            .map(|j| pixels.iter().all(|row| row[j] == '.'))
+            .collect();
+
+        Image {
+            pixels,
+            empty_rows,
+            empty_columns,
+            row_expansion: Expansion::Uniform(1),
+            column_expansion: Expansion::Uniform(1),
+        }
+    }
+
+    // Expands rows and columns alike
+    pub fn set_expansion(&mut self, expansion: Expansion) {
+        self.row_expansion = expansion.clone();
+        self.column_expansion = expansion;
+    }
+
+    pub fn find_galaxies(&self) -> Vec<Point> {
+        let mut galaxies: Vec<Point> = Vec::new();
+        for (i, row) in self.pixels.iter().enumerate() {
+            for (j, pixel) in row.iter().enumerate() {
+                if *pixel == '#' {
+                    galaxies.push((i, j));
+                }
+            }
+        }
+        galaxies
+    }
+
+    // Galaxy coordinates after every empty row and column expanded
+    pub fn expanded_galaxies(&self) -> Vec<(u64, u64)> {
+        let row_offsets = expanded_offsets(&self.row_expansion.widths(&self.empty_rows));
+        let column_offsets = expanded_offsets(&self.column_expansion.widths(&self.empty_columns));
+        self.find_galaxies()
+            .into_iter()
+            .map(|(i, j)| (row_offsets[i], column_offsets[j]))
+            .collect()
+    }
+
+    // Manhattan distances are independent per axis, so each axis is summed on its own
+    pub fn sum_of_shortest_distances(&self) -> u64 {
+        let (mut rows, mut columns): (Vec<u64>, Vec<u64>) =
+            self.expanded_galaxies().into_iter().unzip();
+        sum_of_pairwise_differences(&mut rows) + sum_of_pairwise_differences(&mut columns)
+    }
+}
+
+// Expanded coordinate of every line, as a prefix sum of the line widths before it
+fn expanded_offsets(widths: &[u64]) -> Vec<u64> {
+    let mut offsets: Vec<u64> = Vec::with_capacity(widths.len());
+    let mut offset: u64 = 0;
+    for width in widths {
+        offsets.push(offset);
+        offset = overflow::add(offset, *width, "expanded coordinate");
+    }
+    offsets
+}
+
+// Once sorted, every coordinate is greater than or equal to all the ones before it
+fn sum_of_pairwise_differences(coordinates: &mut [u64]) -> u64 {
+    coordinates.sort_unstable();
+    let mut sum: u64 = 0;
+    let mut prefix_sum: u64 = 0;
+    for (k, coordinate) in coordinates.iter().enumerate() {
+        let distances = overflow::mul(*coordinate, k as u64, "galaxy distances") - prefix_sum;
+        sum = overflow::add(sum, distances, "sum of galaxy distances");
+        prefix_sum = overflow::add(prefix_sum, *coordinate, "sum of galaxy distances");
+    }
+    sum
+}
+
+impl FromStr for Image {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pixels = s.lines().map(|line| line.chars().collect()).collect();
+        Ok(Image::new(pixels))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Expansion, Image};
+    use indoc::indoc;
+
+    const SAMPLE: &str = indoc! {"
+        ...#......
+        .......#..
+        #.........
+        ..........
+        ......#...
+        .#........
+        .........#
+        ..........
+        .......#..
+        #...#.....
+    "};
+
+    #[test]
+    fn expands_rows_and_columns_separately() {
+        let mut image: Image = SAMPLE.parse().unwrap();
+        image.row_expansion = Expansion::Uniform(10);
+        image.column_expansion = Expansion::Uniform(100);
+
+        let sum = image.sum_of_shortest_distances();
+
+        assert_eq!(sum, 5170);
+    }
+
+    #[test]
+    fn expands_by_run_length() {
+        let image = indoc! {"
+            #...#
+            .....
+            .....
+            #....
+        "};
+        let mut image: Image = image.parse().unwrap();
+        image.set_expansion(Expansion::RunLength(|run_length| run_length as u64 * 10));
+
+        let galaxies = image.expanded_galaxies();
+        let sum = image.sum_of_shortest_distances();
+
+        assert_eq!(galaxies, vec![(0, 0), (0, 91), (41, 0)]);
+        assert_eq!(sum, 264);
+    }
+
+    #[test]
+    fn expands_by_weight_table() {
+        let mut image: Image = SAMPLE.parse().unwrap();
+        image.row_expansion = Expansion::Weights(vec![0, 0, 0, 5, 0, 0, 0, 7]);
+
+        let sum = image.sum_of_shortest_distances();
+
+        assert_eq!(sum, 472);
+    }
+
+    #[test]
+    fn sums_distances_of_a_million_galaxies() {
+        let row = "#".repeat(1000);
+        let input = vec![row.as_str(); 1000].join("\n");
+        let image: Image = input.parse().unwrap();
+
+        let sum = image.sum_of_shortest_distances();
+
+        assert_eq!(sum, 333_333_000_000_000);
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt::Write;
+
+use crate::Image;
+
+// Galaxies are identified by their index in reading order, starting at 0
+impl Image {
+    pub fn distance_matrix(&self) -> Vec<Vec<u64>> {
+        let galaxies = self.expanded_galaxies();
+        galaxies
+            .iter()
+            .map(|source| {
+                galaxies
+                    .iter()
+                    .map(|destination| distance(*source, *destination))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // The k galaxies closest to the given one with their distances, closest first and ties
+    // broken by id
+    pub fn nearest_galaxies(&self, galaxy: usize, k: usize) -> Vec<(usize, u64)> {
+        let galaxies = self.expanded_galaxies();
+        let source = galaxies[galaxy];
+        let mut distances: Vec<(usize, u64)> = galaxies
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| *id != galaxy)
+            .map(|(id, destination)| (id, distance(source, *destination)))
+            .collect();
+        distances.sort_unstable_by_key(|(id, distance)| (*distance, *id));
+        distances.truncate(k);
+        distances
+    }
+
+    // Rotating by 45 degrees turns Manhattan distances into the larger of the differences along
+    // either diagonal, so the farthest pair is found from the extremes of each diagonal
+    pub fn farthest_pair(&self) -> Option<(usize, usize, u64)> {
+        let galaxies = self.expanded_galaxies();
+        if galaxies.len() < 2 {
+            return None;
+        }
+
+        let diagonals: [fn((u64, u64)) -> i128; 2] = [
+            |(row, column)| row as i128 + column as i128,
+            |(row, column)| row as i128 - column as i128,
+        ];
+        diagonals
+            .iter()
+            .map(|diagonal| {
+                let projected = |id: &usize| diagonal(galaxies[*id]);
+                let ids = 0..galaxies.len();
+                let lowest = ids.clone().min_by_key(projected).unwrap();
+                let highest = ids.max_by_key(projected).unwrap();
+                let pair = (lowest.min(highest), lowest.max(highest));
+                (pair.0, pair.1, distance(galaxies[pair.0], galaxies[pair.1]))
+            })
+            .max_by_key(|(_, _, distance)| *distance)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("id,row,column,expanded_row,expanded_column\n");
+        for (id, ((row, column), (expanded_row, expanded_column))) in self
+            .find_galaxies()
+            .into_iter()
+            .zip(self.expanded_galaxies())
+            .enumerate()
+        {
+            writeln!(csv, "{id},{row},{column},{expanded_row},{expanded_column}").unwrap();
+        }
+        csv
+    }
+}
+
+fn distance(source: (u64, u64), destination: (u64, u64)) -> u64 {
+    overflow::add(
+        source.0.abs_diff(destination.0),
+        source.1.abs_diff(destination.1),
+        "galaxy distance",
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Expansion, Image};
+    use indoc::indoc;
+
+    fn sample() -> Image {
+        let mut image: Image = indoc! {"
+            ...#......
+            .......#..
+            #.........
+            ..........
+            ......#...
+            .#........
+            .........#
+            ..........
+            .......#..
+            #...#.....
+        "}
+        .parse()
+        .unwrap();
+        image.set_expansion(Expansion::Uniform(2));
+        image
+    }
+
+    #[test]
+    fn builds_distance_matrix() {
+        let image = sample();
+
+        let matrix = image.distance_matrix();
+
+        assert_eq!(matrix.len(), 9);
+        assert_eq!(matrix[0], vec![0, 6, 6, 9, 9, 15, 15, 15, 12]);
+        assert_eq!(matrix[4][8], 9);
+        assert_eq!(matrix[8][4], 9);
+    }
+
+    #[test]
+    fn finds_nearest_galaxies() {
+        let image = sample();
+
+        let nearest = image.nearest_galaxies(4, 3);
+
+        assert_eq!(nearest, vec![(2, 5), (7, 6), (3, 8)]);
+    }
+
+    #[test]
+    fn finds_farthest_pair() {
+        let image = sample();
+
+        let pair = image.farthest_pair();
+
+        assert_eq!(pair, Some((1, 7, 19)));
+    }
+
+    #[test]
+    fn finds_farthest_pair_beyond_i64() {
+        let mut image: Image = "##.#".parse().unwrap();
+        image.column_expansion = Expansion::Weights(vec![0, 0, 1 << 63]);
+
+        let pair = image.farthest_pair();
+
+        assert_eq!(pair, Some((0, 2, (1 << 63) + 2)));
+    }
+
+    #[test]
+    fn exports_csv() {
+        let image: Image = "#..\n...\n..#\n".parse().unwrap();
+
+        let csv = image.to_csv();
+
+        assert_eq!(
+            csv,
+            indoc! {"
+                id,row,column,expanded_row,expanded_column
+                0,0,0,0,0
+                1,2,2,2,2
+            "}
+        );
+    }
+}
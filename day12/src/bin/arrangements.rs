@@ -0,0 +1,37 @@
+use std::env;
+
+use day12::ConditionRecord;
+
+// Usage: arrangements "<springs> <groups>" [--unfold <factor>] [--separator <char>] [--limit <n>]
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut record: ConditionRecord = args
+        .first()
+        .expect("Missing condition record")
+        .parse()
+        .expect("Invalid condition record");
+
+    let mut unfold_factor: usize = 1;
+    let mut separator: char = '?';
+    let mut limit: Option<usize> = None;
+    for option in args[1..].chunks(2) {
+        let value = option.get(1).expect("Missing option value");
+        match option[0].as_str() {
+            "--unfold" => unfold_factor = value.parse().expect("Invalid unfold factor"),
+            "--separator" => separator = value.parse().expect("Invalid separator"),
+            "--limit" => limit = Some(value.parse().expect("Invalid limit")),
+            other => panic!("Unknown option: {other}"),
+        }
+    }
+    record = record.unfold(unfold_factor, separator);
+
+    let mut listed = 0;
+    for arrangement in record.arrangements(limit) {
+        println!("{arrangement}");
+        listed += 1;
+    }
+    println!(
+        "Listed {listed} of {} arrangements",
+        record.count_combinations()
+    );
+}
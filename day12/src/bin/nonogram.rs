@@ -0,0 +1,27 @@
+use std::{env, fs};
+
+use day12::nonogram::{Nonogram, Solution};
+
+// Usage: nonogram <puzzle file>
+fn main() {
+    let path = env::args().nth(1).expect("Missing puzzle file");
+    let input = fs::read_to_string(path).unwrap();
+    let nonogram: Nonogram = input.parse().expect("Invalid nonogram");
+
+    match nonogram.solve() {
+        Solution::Unique(grid) => print_grid(&grid),
+        Solution::Multiple(first, second) => {
+            println!("Multiple solutions, for example:");
+            print_grid(&first);
+            println!();
+            print_grid(&second);
+        }
+        Solution::None => println!("No solution"),
+    }
+}
+
+fn print_grid(grid: &[Vec<char>]) {
+    for row in grid {
+        println!("{}", row.iter().collect::<String>());
+    }
+}
@@ -0,0 +1,284 @@
+pub mod nonogram;
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct ConditionRecord {
+    pub springs: Vec<char>,
+    pub damaged_groups: Vec<u32>,
+}
+
+struct CombinationsTable {
+    width: usize,
+    // How many springs, starting at each index, could all be damaged
+    possibly_damaged_run: Vec<usize>,
+    // combinations[spring_index * width + group_index] holds the number of arrangements of
+    // springs[spring_index..] that match damaged_groups[group_index..]
+    combinations: Vec<u128>,
+}
+
+pub struct Arrangements<'a> {
+    record: &'a ConditionRecord,
+    table: CombinationsTable,
+    stack: Vec<(usize, usize, String)>,
+    remaining: Option<usize>,
+}
+
+impl ConditionRecord {
+    pub fn unfold(&self, factor: usize, separator: char) -> ConditionRecord {
+        let mut springs: Vec<char> = Vec::new();
+        for copy in 0..factor {
+            if copy > 0 {
+                springs.push(separator);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        ConditionRecord {
+            springs,
+            damaged_groups: self.damaged_groups.repeat(factor),
+        }
+    }
+
+    pub fn count_combinations(&self) -> u128 {
+        self.combinations_table().get(0, 0)
+    }
+
+    // Lazily yields every spring string matching this record, stopping after `limit` if given
+    pub fn arrangements(&self, limit: Option<usize>) -> Arrangements<'_> {
+        let table = self.combinations_table();
+        let stack = if table.get(0, 0) > 0 {
+            vec![(0, 0, String::with_capacity(self.springs.len()))]
+        } else {
+            Vec::new()
+        };
+
+        Arrangements {
+            record: self,
+            table,
+            stack,
+            remaining: limit,
+        }
+    }
+
+    fn combinations_table(&self) -> CombinationsTable {
+        let springs_count = self.springs.len();
+        let groups_count = self.damaged_groups.len();
+
+        let mut possibly_damaged_run: Vec<usize> = vec![0; springs_count + 1];
+        for spring_index in (0..springs_count).rev() {
+            possibly_damaged_run[spring_index] = match self.springs[spring_index] {
+                '.' => 0,
+                '#' | '?' => possibly_damaged_run[spring_index + 1] + 1,
+                spring => panic!("Unknown spring condition: {spring}"),
+            };
+        }
+
+        let width = groups_count + 1;
+        let mut table = CombinationsTable {
+            width,
+            possibly_damaged_run,
+            combinations: vec![0; (springs_count + 1) * width],
+        };
+        table.combinations[springs_count * width + groups_count] = 1;
+
+        for spring_index in (0..springs_count).rev() {
+            for group_index in 0..=groups_count {
+                let mut count = 0;
+
+                if self.springs[spring_index] != '#' {
+                    count = overflow::add(
+                        count,
+                        table.get(spring_index + 1, group_index),
+                        "arrangements",
+                    );
+                }
+
+                if let Some(next_spring_index) =
+                    self.next_spring_after_group(&table, spring_index, group_index)
+                {
+                    count = overflow::add(
+                        count,
+                        table.get(next_spring_index, group_index + 1),
+                        "arrangements",
+                    );
+                }
+
+                table.combinations[spring_index * width + group_index] = count;
+            }
+        }
+
+        table
+    }
+
+    // If the damaged group at group_index can start at spring_index, returns the index of the
+    // first spring after it and its trailing operational spring
+    fn next_spring_after_group(
+        &self,
+        table: &CombinationsTable,
+        spring_index: usize,
+        group_index: usize,
+    ) -> Option<usize> {
+        if self.springs[spring_index] == '.' || group_index == self.damaged_groups.len() {
+            return None;
+        }
+
+        let springs_count = self.springs.len();
+        let group_size = self.damaged_groups[group_index] as usize;
+        let group_end = spring_index + group_size;
+        if table.possibly_damaged_run[spring_index] < group_size
+            || (group_end < springs_count && self.springs[group_end] == '#')
+        {
+            return None;
+        }
+
+        Some((group_end + 1).min(springs_count))
+    }
+}
+
+impl CombinationsTable {
+    fn get(&self, spring_index: usize, group_index: usize) -> u128 {
+        self.combinations[spring_index * self.width + group_index]
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        let springs_count = self.record.springs.len();
+        while let Some((spring_index, group_index, arrangement)) = self.stack.pop() {
+            if spring_index == springs_count {
+                if let Some(remaining) = self.remaining.as_mut() {
+                    *remaining -= 1;
+                }
+                return Some(arrangement);
+            }
+
+            if self.record.springs[spring_index] != '#'
+                && self.table.get(spring_index + 1, group_index) > 0
+            {
+                let mut next_arrangement = arrangement.clone();
+                next_arrangement.push('.');
+                self.stack
+                    .push((spring_index + 1, group_index, next_arrangement));
+            }
+
+            if let Some(next_spring_index) =
+                self.record
+                    .next_spring_after_group(&self.table, spring_index, group_index)
+            {
+                if self.table.get(next_spring_index, group_index + 1) > 0 {
+                    let mut next_arrangement = arrangement;
+                    let group_size = self.record.damaged_groups[group_index] as usize;
+                    next_arrangement.extend(std::iter::repeat_n('#', group_size));
+                    if next_spring_index > spring_index + group_size {
+                        next_arrangement.push('.');
+                    }
+                    self.stack
+                        .push((next_spring_index, group_index + 1, next_arrangement));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl FromStr for ConditionRecord {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split: Vec<&str> = s.split_whitespace().collect();
+        let springs: Vec<char> = split[0].chars().collect();
+        let damaged_groups: Vec<u32> = split[1].split(",").map(|n| n.parse().unwrap()).collect();
+
+        Ok(ConditionRecord {
+            springs,
+            damaged_groups,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConditionRecord;
+    use test_case::test_case;
+
+    #[test]
+    fn counts_long_records() {
+        let record = ConditionRecord {
+            springs: vec!['?'; 100],
+            damaged_groups: vec![1; 20],
+        };
+
+        let combinations = record.count_combinations();
+
+        assert_eq!(combinations, 4694436188839116720);
+    }
+
+    #[test_case("???.### 1,1,3", 2, '?', "???.###????.### 1,1,3,1,1,3")]
+    #[test_case(".# 1", 3, '.', ".#..#..# 1,1,1")]
+    fn unfolds_records(input: &str, factor: usize, separator: char, expected: &str) {
+        let record: ConditionRecord = input.parse().unwrap();
+        let expected_record: ConditionRecord = expected.parse().unwrap();
+
+        let unfolded = record.unfold(factor, separator);
+
+        assert_eq!(unfolded.springs, expected_record.springs);
+        assert_eq!(unfolded.damaged_groups, expected_record.damaged_groups);
+    }
+
+    #[test]
+    fn lists_arrangements() {
+        let record: ConditionRecord = "?###???????? 3,2,1".parse().unwrap();
+
+        let arrangements: Vec<String> = record.arrangements(None).collect();
+
+        assert_eq!(
+            arrangements,
+            vec![
+                ".###.##.#...",
+                ".###.##..#..",
+                ".###.##...#.",
+                ".###.##....#",
+                ".###..##.#..",
+                ".###..##..#.",
+                ".###..##...#",
+                ".###...##.#.",
+                ".###...##..#",
+                ".###....##.#",
+            ]
+        );
+    }
+
+    #[test_case(".??..??...?##. 1,1,3", 5)]
+    #[test_case("?###???????? 3,2,1", 5)]
+    #[test_case("????.######..#####. 1,6,5", 2)]
+    fn lists_as_many_arrangements_as_counted(input: &str, factor: usize) {
+        let record = input
+            .parse::<ConditionRecord>()
+            .unwrap()
+            .unfold(factor, '?');
+
+        let listed = record.arrangements(None).count() as u128;
+
+        assert_eq!(listed, record.count_combinations());
+    }
+
+    #[test]
+    fn limits_arrangements() {
+        let record: ConditionRecord = "?###???????? 3,2,1".parse().unwrap();
+
+        let arrangements: Vec<String> = record.arrangements(Some(3)).collect();
+
+        assert_eq!(
+            arrangements,
+            vec![".###.##.#...", ".###.##..#..", ".###.##...#."]
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use std::str::FromStr;
+
+use crate::ConditionRecord;
+
+type Grid = Vec<Vec<char>>;
+
+// Row clues, a blank line, column clues and, optionally, a blank line followed by a partial
+// grid of '#', '.' and '?'. Clues are comma separated like in condition records, with 0 for an
+// empty line.
+#[derive(Debug, Clone)]
+pub struct Nonogram {
+    row_clues: Vec<Vec<u32>>,
+    column_clues: Vec<Vec<u32>>,
+    grid: Grid,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Solution {
+    Unique(Grid),
+    Multiple(Grid, Grid),
+    None,
+}
+
+impl Nonogram {
+    pub fn solve(&self) -> Solution {
+        let mut solutions: Vec<Grid> = Vec::new();
+        self.search(self.grid.clone(), &mut solutions);
+
+        let mut solutions = solutions.into_iter();
+        match (solutions.next(), solutions.next()) {
+            (Some(first), Some(second)) => Solution::Multiple(first, second),
+            (Some(solution), None) => Solution::Unique(solution),
+            _ => Solution::None,
+        }
+    }
+
+    fn search(&self, mut grid: Grid, solutions: &mut Vec<Grid>) {
+        if solutions.len() > 1 || !self.propagate(&mut grid) {
+            return;
+        }
+
+        let unknown_cell = grid.iter().enumerate().find_map(|(row, cells)| {
+            cells
+                .iter()
+                .position(|cell| cell == &'?')
+                .map(|column| (row, column))
+        });
+
+        match unknown_cell {
+            None => solutions.push(grid),
+            Some((row, column)) => {
+                for cell in ['#', '.'] {
+                    let mut guess = grid.clone();
+                    guess[row][column] = cell;
+                    self.search(guess, solutions);
+                }
+            }
+        }
+    }
+
+    // Pins down cells line by line until nothing changes, returning false on a contradiction
+    fn propagate(&self, grid: &mut Grid) -> bool {
+        let height = self.row_clues.len();
+        let width = self.column_clues.len();
+        let mut dirty_rows = vec![true; height];
+        let mut dirty_columns = vec![true; width];
+
+        loop {
+            let mut changed = false;
+
+            for row in 0..height {
+                if !dirty_rows[row] {
+                    continue;
+                }
+                dirty_rows[row] = false;
+
+                let Some(solved) = solve_line(&grid[row], &self.row_clues[row]) else {
+                    return false;
+                };
+                for column in 0..width {
+                    if grid[row][column] != solved[column] {
+                        grid[row][column] = solved[column];
+                        dirty_columns[column] = true;
+                        changed = true;
+                    }
+                }
+            }
+
+            for column in 0..width {
+                if !dirty_columns[column] {
+                    continue;
+                }
+                dirty_columns[column] = false;
+
+                let cells: Vec<char> = grid.iter().map(|cells| cells[column]).collect();
+                let Some(solved) = solve_line(&cells, &self.column_clues[column]) else {
+                    return false;
+                };
+                for row in 0..height {
+                    if grid[row][column] != solved[row] {
+                        grid[row][column] = solved[row];
+                        dirty_rows[row] = true;
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                return true;
+            }
+        }
+    }
+}
+
+// Fixes every unknown cell that has the same state in all arrangements of the line
+fn solve_line(cells: &[char], clue: &[u32]) -> Option<Vec<char>> {
+    let mut record = ConditionRecord {
+        springs: cells.to_vec(),
+        damaged_groups: clue.to_vec(),
+    };
+    if record.count_combinations() == 0 {
+        return None;
+    }
+
+    for (index, cell) in cells.iter().enumerate() {
+        if cell != &'?' {
+            continue;
+        }
+
+        record.springs[index] = '#';
+        let can_be_damaged = record.count_combinations() > 0;
+        record.springs[index] = '.';
+        let can_be_operational = record.count_combinations() > 0;
+
+        record.springs[index] = match (can_be_damaged, can_be_operational) {
+            (true, false) => '#',
+            (false, true) => '.',
+            _ => '?',
+        };
+    }
+
+    Some(record.springs)
+}
+
+impl FromStr for Nonogram {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = s.trim().split("\n\n");
+        let parse_clues = |section: Option<&str>| -> Result<Vec<Vec<u32>>, ()> {
+            section
+                .ok_or(())?
+                .lines()
+                .map(|line| {
+                    line.trim()
+                        .split(",")
+                        .map(|n| n.parse::<u32>().map_err(|_| ()))
+                        .filter(|n| n != &Ok(0))
+                        .collect()
+                })
+                .collect()
+        };
+        let row_clues = parse_clues(sections.next())?;
+        let column_clues = parse_clues(sections.next())?;
+
+        let grid: Grid = match sections.next() {
+            Some(section) => section
+                .lines()
+                .map(|line| line.trim().chars().collect())
+                .collect(),
+            None => vec![vec!['?'; column_clues.len()]; row_clues.len()],
+        };
+        if grid.len() != row_clues.len()
+            || grid.iter().any(|cells| {
+                cells.len() != column_clues.len()
+                    || cells.iter().any(|cell| !['#', '.', '?'].contains(cell))
+            })
+        {
+            return Err(());
+        }
+
+        Ok(Nonogram {
+            row_clues,
+            column_clues,
+            grid,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Nonogram, Solution};
+    use indoc::indoc;
+
+    fn grid(picture: &str) -> Vec<Vec<char>> {
+        picture.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    #[test]
+    fn solves_unique_picture() {
+        let nonogram: Nonogram = indoc! {"
+            3
+            1,1
+            5
+            1,1
+            1,1
+
+            4
+            1,1
+            1,1
+            1,1
+            4
+        "}
+        .parse()
+        .unwrap();
+
+        let solution = nonogram.solve();
+
+        assert_eq!(
+            solution,
+            Solution::Unique(grid(indoc! {"
+                .###.
+                #...#
+                #####
+                #...#
+                #...#
+            "}))
+        );
+    }
+
+    #[test]
+    fn reports_multiple_solutions() {
+        let nonogram: Nonogram = indoc! {"
+            1
+            1
+
+            1
+            1
+        "}
+        .parse()
+        .unwrap();
+
+        let solution = nonogram.solve();
+
+        assert_eq!(solution, Solution::Multiple(grid("#.\n.#"), grid(".#\n#.")));
+    }
+
+    #[test]
+    fn uses_partial_grid() {
+        let nonogram: Nonogram = indoc! {"
+            1
+            1
+
+            1
+            1
+
+            ?#
+            ??
+        "}
+        .parse()
+        .unwrap();
+
+        let solution = nonogram.solve();
+
+        assert_eq!(solution, Solution::Unique(grid(".#\n#.")));
+    }
+
+    #[test]
+    fn reports_no_solution() {
+        let nonogram: Nonogram = indoc! {"
+            2
+            0
+
+            1
+            0
+        "}
+        .parse()
+        .unwrap();
+
+        let solution = nonogram.solve();
+
+        assert_eq!(solution, Solution::None);
+    }
+}
@@ -0,0 +1,278 @@
+use std::{cmp::min, str::FromStr};
+
+#[derive(Debug)]
+pub struct PatternList {
+    pub patterns: Vec<Pattern>,
+}
+
+#[derive(Debug)]
+pub struct Pattern {
+    rows: LineMasks,
+    columns: LineMasks,
+}
+
+// Each line (row or column) as a bitmask of its '#' cells, split into as many u64 words as needed
+#[derive(Debug)]
+struct LineMasks {
+    n_lines: usize,
+    words_per_line: usize,
+    words: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+// A mirror between rows (horizontal) or columns (vertical) `index - 1` and `index`
+#[derive(Debug, PartialEq)]
+pub struct Reflection {
+    pub orientation: Orientation,
+    pub index: usize,
+    pub smudges: Vec<Smudge>,
+}
+
+// A pair of cells, as (row, column), that would mirror each other if either one was flipped
+#[derive(Debug, PartialEq)]
+pub struct Smudge {
+    pub cell: (usize, usize),
+    pub reflected_cell: (usize, usize),
+}
+
+impl Pattern {
+    pub fn new(rows: Vec<Vec<char>>) -> Pattern {
+        let n_rows = rows.len();
+        let n_columns = rows[0].len();
+        Pattern {
+            rows: LineMasks::new(n_rows, n_columns, |i, j| rows[i][j] == '#'),
+            columns: LineMasks::new(n_columns, n_rows, |j, i| rows[i][j] == '#'),
+        }
+    }
+
+    pub fn find_reflections(&self, max_smudges: usize) -> Vec<Reflection> {
+        let horizontal = (1..self.rows.n_lines).filter_map(|i| {
+            self.rows
+                .find_mirror_differences(i, max_smudges)
+                .map(|differences| Reflection {
+                    orientation: Orientation::Horizontal,
+                    index: i,
+                    smudges: differences
+                        .into_iter()
+                        .map(|(row, reflected_row, j)| Smudge {
+                            cell: (row, j),
+                            reflected_cell: (reflected_row, j),
+                        })
+                        .collect(),
+                })
+        });
+        let vertical = (1..self.columns.n_lines).filter_map(|j| {
+            self.columns
+                .find_mirror_differences(j, max_smudges)
+                .map(|differences| Reflection {
+                    orientation: Orientation::Vertical,
+                    index: j,
+                    smudges: differences
+                        .into_iter()
+                        .map(|(column, reflected_column, i)| Smudge {
+                            cell: (i, column),
+                            reflected_cell: (i, reflected_column),
+                        })
+                        .collect(),
+                })
+        });
+        horizontal.chain(vertical).collect()
+    }
+}
+
+impl LineMasks {
+    fn new(n_lines: usize, line_length: usize, is_set: impl Fn(usize, usize) -> bool) -> LineMasks {
+        let words_per_line = line_length.div_ceil(64).max(1);
+        let mut words: Vec<u64> = vec![0; n_lines * words_per_line];
+        for line in 0..n_lines {
+            for position in 0..line_length {
+                if is_set(line, position) {
+                    words[line * words_per_line + position / 64] |= 1 << (position % 64);
+                }
+            }
+        }
+        LineMasks {
+            n_lines,
+            words_per_line,
+            words,
+        }
+    }
+
+    fn line(&self, line: usize) -> &[u64] {
+        &self.words[line * self.words_per_line..(line + 1) * self.words_per_line]
+    }
+
+    // Cells that differ across a mirror between lines `index - 1` and `index`, as
+    // (line, reflected line, position), or None if there are more than max_smudges
+    fn find_mirror_differences(
+        &self,
+        index: usize,
+        max_smudges: usize,
+    ) -> Option<Vec<(usize, usize, usize)>> {
+        let simmetry_half_size = min(index, self.n_lines - index);
+        let line_pairs = (0..simmetry_half_size).map(|d| (index - d - 1, index + d));
+
+        let mut n_differences: usize = 0;
+        for (line, reflected_line) in line_pairs.clone() {
+            n_differences += self
+                .line(line)
+                .iter()
+                .zip(self.line(reflected_line))
+                .map(|(word, reflected_word)| (word ^ reflected_word).count_ones() as usize)
+                .sum::<usize>();
+            if n_differences > max_smudges {
+                return None;
+            }
+        }
+
+        let mut differences: Vec<(usize, usize, usize)> = Vec::with_capacity(n_differences);
+        if n_differences > 0 {
+            for (line, reflected_line) in line_pairs {
+                let word_pairs = self.line(line).iter().zip(self.line(reflected_line));
+                for (word_index, (word, reflected_word)) in word_pairs.enumerate() {
+                    let mut difference = word ^ reflected_word;
+                    while difference != 0 {
+                        let bit = difference.trailing_zeros() as usize;
+                        differences.push((line, reflected_line, word_index * 64 + bit));
+                        difference &= difference - 1;
+                    }
+                }
+            }
+        }
+        Some(differences)
+    }
+}
+
+impl Reflection {
+    pub fn summary(&self) -> u64 {
+        match self.orientation {
+            Orientation::Horizontal => overflow::mul(self.index as u64, 100, "reflection summary"),
+            Orientation::Vertical => self.index as u64,
+        }
+    }
+}
+
+impl FromStr for PatternList {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().peekable();
+        let mut patterns: Vec<Pattern> = Vec::new();
+
+        while lines.peek().is_some() {
+            let pattern_rows: Vec<Vec<char>> = lines
+                .by_ref()
+                .take_while(|l| !l.is_empty())
+                .map(|row| row.chars().collect())
+                .collect();
+            patterns.push(Pattern::new(pattern_rows));
+        }
+
+        Ok(PatternList { patterns })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Orientation, Pattern, Reflection, Smudge};
+    use indoc::indoc;
+    use test_case::test_case;
+
+    fn pattern(input: &str) -> Pattern {
+        Pattern::new(input.lines().map(|row| row.chars().collect()).collect())
+    }
+
+    #[test]
+    fn finds_reflections_with_smudges() {
+        let pattern = pattern(indoc! {"
+            #.##..##.
+            ..#.##.#.
+            ##......#
+            ##......#
+            ..#.##.#.
+            ..##..##.
+            #.#.##.#.
+        "});
+
+        let reflections = pattern.find_reflections(1);
+
+        assert_eq!(
+            reflections,
+            vec![
+                Reflection {
+                    orientation: Orientation::Horizontal,
+                    index: 3,
+                    smudges: vec![Smudge {
+                        cell: (0, 0),
+                        reflected_cell: (5, 0),
+                    }],
+                },
+                Reflection {
+                    orientation: Orientation::Vertical,
+                    index: 5,
+                    smudges: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_reflections_above_tolerance() {
+        let pattern = pattern(indoc! {"
+            #...##..#
+            #....#..#
+            ..##..###
+            #####.##.
+            #####.##.
+            ..##..###
+            #....#..#
+        "});
+
+        let reflections = pattern.find_reflections(0);
+
+        assert_eq!(
+            reflections,
+            vec![Reflection {
+                orientation: Orientation::Horizontal,
+                index: 4,
+                smudges: vec![],
+            }]
+        );
+    }
+
+    #[test_case(63)]
+    #[test_case(64)]
+    #[test_case(150)]
+    fn finds_reflections_in_wide_patterns(width: usize) {
+        let mut rows: Vec<Vec<char>> = vec![vec!['.'; width]; 4];
+        for row in [0, 3] {
+            rows[row][width - 1] = '#';
+            rows[row][width - 3] = '#';
+        }
+        rows[1][width - 2] = '#';
+        let pattern = Pattern::new(rows);
+
+        let reflections: Vec<Reflection> = pattern
+            .find_reflections(1)
+            .into_iter()
+            .filter(|reflection| reflection.orientation == Orientation::Horizontal)
+            .collect();
+
+        assert_eq!(
+            reflections,
+            vec![Reflection {
+                orientation: Orientation::Horizontal,
+                index: 2,
+                smudges: vec![Smudge {
+                    cell: (1, width - 2),
+                    reflected_cell: (2, width - 2),
+                }],
+            }]
+        );
+    }
+}
@@ -55,9 +55,10 @@ impl Platform {
     fn calculate_load(&self) -> u64 {
         let mut load: u64 = 0;
         for (i, row) in self.rows.iter().enumerate() {
+            let _line = overflow::on_line(i + 1);
             for c in row.iter() {
                 if *c == 'O' {
-                    load += (self.n_rows - i) as u64
+                    load = overflow::add(load, (self.n_rows - i) as u64, "total load");
                 }
             }
         }
@@ -0,0 +1,70 @@
+use std::{env, process};
+
+use day15::{
+    hasher::{BucketStatistics, Hasher, MultiplicativeHasher, StdHasher},
+    reader::StepReader,
+    LensBoxes, Step,
+};
+
+const USAGE: &str = "Usage: hash_stats [multiplier] [table size]";
+
+fn main() {
+    let (multiplier, table_size) = match parse_args(env::args().skip(1).collect()) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}");
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+    let multiplicative = MultiplicativeHasher::new(multiplier, table_size);
+    let fx = StdHasher::fx(table_size);
+
+    let input = include_str!("../../input.txt");
+    let steps: Vec<Step> = StepReader::new(input.as_bytes())
+        .map(|step| step.unwrap_or_else(|error| panic!("{error}")))
+        .collect();
+
+    println!(
+        "x{} mod {}",
+        multiplicative.multiplier(),
+        multiplicative.table_size()
+    );
+    report(multiplicative, &steps);
+    println!();
+    println!("FxHash mod {}", fx.table_size());
+    report(fx, &steps);
+}
+
+fn parse_args(args: Vec<String>) -> Result<(u64, u64), String> {
+    let numbers: Vec<u64> = args
+        .iter()
+        .map(|arg| arg.parse().map_err(|_| format!("Invalid number '{arg}'")))
+        .collect::<Result<_, _>>()?;
+    if numbers.len() > 2 {
+        return Err("Too many arguments".to_owned());
+    }
+    let multiplier = numbers.first().copied().unwrap_or(17);
+    let table_size = numbers.get(1).copied().unwrap_or(256);
+    if table_size == 0 {
+        return Err("Table size must be positive".to_owned());
+    }
+    Ok((multiplier, table_size))
+}
+
+fn report<H: Hasher>(hasher: H, steps: &[Step]) {
+    let statistics = BucketStatistics::new(&hasher, steps.iter().map(|step| step.label.as_str()));
+    println!("  labels: {}", statistics.label_count);
+    println!("  load factor: {:.3}", statistics.load_factor);
+    println!("  longest chain: {}", statistics.longest_chain);
+    println!("  collisions: {}", statistics.collisions);
+    for (chain_length, buckets) in statistics.chain_length_histogram.iter().enumerate() {
+        println!("  buckets with {chain_length} labels: {buckets}");
+    }
+
+    let mut boxes = LensBoxes::with_hasher(hasher);
+    for step in steps {
+        boxes.apply(step);
+    }
+    println!("  focusing power: {}", boxes.focusing_power());
+}
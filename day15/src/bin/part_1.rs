@@ -1,20 +1,15 @@
+use day15::{hash, reader::RawStepReader};
+
 fn main() {
     let input = include_str!("../../input.txt");
     println!("{}", solve(input));
 }
 
 fn solve(input: &str) -> u64 {
-    let steps = input.split(",");
-    steps.map(hash).sum()
-}
-
-fn hash(s: &str) -> u64 {
-    let mut result: u64 = 0;
-    for c in s.bytes() {
-        result += c as u64;
-        result = (result * 17) % 256;
-    }
-    result
+    overflow::sum(
+        RawStepReader::new(input.as_bytes()).map(|step| hash(&step.unwrap().text)),
+        "sum of hashes",
+    )
 }
 
 #[cfg(test)]
@@ -1,101 +1,38 @@
-use std::str::FromStr;
+use std::{
+    env,
+    fs::File,
+    io::{BufRead, BufReader},
+};
 
-fn main() {
-    let input = include_str!("../../input.txt");
-    println!("{}", solve(input));
-}
-
-#[derive(Debug)]
-struct Step {
-    label: String,
-    operation: Operation,
-}
+use day15::{reader::StepReader, LensBoxes};
 
-#[derive(Debug, Clone)]
-struct Lens {
-    label: String,
-    focal_length: u8,
-}
-
-#[derive(Debug)]
-enum Operation {
-    ADD { focal_length: u8 },
-    REMOVE,
-}
-
-fn solve(input: &str) -> u64 {
-    let steps: Vec<Step> = input.split(",").map(|s| s.parse().unwrap()).collect();
-    let mut boxes: Vec<Vec<Lens>> = vec![Vec::new(); 256];
-
-    for step in steps.iter() {
-        let i = hash(&step.label) as usize;
-        let existing_lens = boxes[i]
-            .iter()
-            .enumerate()
-            .find(|(_, lens)| lens.label == step.label);
-
-        match step.operation {
-            Operation::ADD { focal_length } => {
-                let new_lens = Lens {
-                    label: step.label.to_owned(),
-                    focal_length,
-                };
-
-                if let Some((j, _)) = existing_lens {
-                    boxes[i][j] = new_lens;
-                } else {
-                    boxes[i].push(new_lens);
-                }
-            }
-            Operation::REMOVE => {
-                if let Some((j, _)) = existing_lens {
-                    boxes[i].remove(j);
-                }
-            }
+// Reads the puzzle input, or streams the init sequence from a file if a path is given
+fn main() {
+    match env::args().nth(1) {
+        Some(path) => {
+            let file = File::open(path).unwrap();
+            println!("{}", solve_from(StepReader::new(BufReader::new(file))));
+        }
+        None => {
+            let input = include_str!("../../input.txt");
+            println!("{}", solve(input));
         }
     }
-
-    boxes
-        .iter()
-        .enumerate()
-        .map(|(i, lenses)| -> u64 {
-            lenses
-                .iter()
-                .enumerate()
-                .map(|(j, lense)| (i as u64 + 1) * (j as u64 + 1) * lense.focal_length as u64)
-                .sum()
-        })
-        .sum()
 }
 
-impl FromStr for Step {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(label) = s.strip_suffix('-') {
-            return Ok(Step {
-                label: label.to_owned(),
-                operation: Operation::REMOVE,
-            });
-        }
-
-        let key_value_split: Vec<&str> = s.split('=').collect();
-        let label = key_value_split[0];
-        let focal_length: u8 = key_value_split[1].parse().unwrap();
-        Ok(Step {
-            label: label.to_owned(),
-            operation: Operation::ADD { focal_length },
-        })
-    }
+fn solve(input: &str) -> u64 {
+    solve_from(StepReader::new(input.as_bytes()))
 }
 
-fn hash(s: &str) -> u64 {
-    let mut result: u64 = 0;
-    for c in s.bytes() {
-        result += c as u64;
-        result = (result * 17) % 256;
+fn solve_from<R: BufRead>(steps: StepReader<R>) -> u64 {
+    let mut boxes = LensBoxes::new();
+    for step in steps {
+        match step {
+            Ok(step) => boxes.apply(&step),
+            Err(error) => panic!("{error}"),
+        };
     }
-    result
+    boxes.focusing_power()
 }
 
 #[cfg(test)]
@@ -108,4 +45,11 @@ mod test {
 
         assert_eq!(solution, 145);
     }
+
+    #[test]
+    fn ignores_trailing_newline() {
+        let solution = solve("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n");
+
+        assert_eq!(solution, 145);
+    }
 }
@@ -0,0 +1,136 @@
+use std::io::{self, BufRead, Write};
+
+use day15::{Lens, LensBoxes, Step};
+
+// Reads one step (e.g. `rn=1` or `cm-`) or command (`undo`, `dump`, `power`) per line
+fn main() {
+    let mut session = Session::new();
+    let stdin = io::stdin();
+
+    prompt();
+    for line in stdin.lock().lines() {
+        let line = line.unwrap();
+        let command = line.trim();
+        if command == "quit" || command == "exit" {
+            break;
+        }
+        if !command.is_empty() {
+            println!("{}", session.execute(command));
+        }
+        prompt();
+    }
+}
+
+fn prompt() {
+    print!("> ");
+    io::stdout().flush().unwrap();
+}
+
+struct Session {
+    boxes: LensBoxes,
+    history: Vec<Step>,
+}
+
+impl Session {
+    fn new() -> Session {
+        Session {
+            boxes: LensBoxes::new(),
+            history: Vec::new(),
+        }
+    }
+
+    fn execute(&mut self, command: &str) -> String {
+        match command {
+            "undo" => self.undo(),
+            "dump" => self.dump(),
+            "power" => self.boxes.focusing_power().to_string(),
+            _ => match command.parse::<Step>() {
+                Ok(step) => {
+                    let i = self.boxes.apply(&step);
+                    self.history.push(step);
+                    format_box(i, &self.boxes.box_contents(i))
+                }
+                Err(_) => format!("Unknown step or command: {command}"),
+            },
+        }
+    }
+
+    // Steps can't be reverted in place, so the boxes are rebuilt from the remaining history
+    fn undo(&mut self) -> String {
+        let Some(undone) = self.history.pop() else {
+            return "Nothing to undo".to_owned();
+        };
+
+        self.boxes = LensBoxes::new();
+        for step in self.history.iter() {
+            self.boxes.apply(step);
+        }
+
+        let i = self.boxes.box_index(&undone.label);
+        format_box(i, &self.boxes.box_contents(i))
+    }
+
+    fn dump(&self) -> String {
+        let boxes: Vec<String> = self
+            .boxes
+            .non_empty_boxes()
+            .map(|(i, lenses)| format_box(i, &lenses))
+            .collect();
+        if boxes.is_empty() {
+            return "All boxes are empty".to_owned();
+        }
+        boxes.join("\n")
+    }
+}
+
+fn format_box(i: usize, lenses: &[&Lens]) -> String {
+    let lenses: Vec<String> = lenses
+        .iter()
+        .map(|lens| format!("[{} {}]", lens.label, lens.focal_length))
+        .collect();
+    format!("Box {i}: {}", lenses.join(" "))
+        .trim_end()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::Session;
+
+    #[test]
+    fn prints_changed_box() {
+        let mut session = Session::new();
+
+        session.execute("rn=1");
+        let output = session.execute("cm=2");
+
+        assert_eq!(output, "Box 0: [rn 1] [cm 2]");
+    }
+
+    #[test]
+    fn undoes_steps() {
+        let mut session = Session::new();
+        for step in "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6".split(",") {
+            session.execute(step);
+        }
+
+        let output = session.execute("undo");
+
+        assert_eq!(output, "Box 3: [ot 9] [ab 5]");
+        assert_eq!(
+            session.execute("dump"),
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 9] [ab 5]"
+        );
+        assert_eq!(session.execute("power"), "81");
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        let mut session = Session::new();
+
+        let output = session.execute("rn=x");
+
+        assert_eq!(output, "Unknown step or command: rn=x");
+        assert_eq!(session.execute("undo"), "Nothing to undo");
+    }
+}
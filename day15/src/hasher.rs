@@ -0,0 +1,195 @@
+use std::{collections::HashSet, hash::BuildHasher};
+
+use rustc_hash::FxBuildHasher;
+
+// Maps a label to one of `bucket_count` boxes
+pub trait Hasher {
+    fn bucket_count(&self) -> usize;
+
+    fn bucket(&self, label: &str) -> usize;
+}
+
+// The puzzle's HASH generalised: for each byte, add it, multiply and take the remainder
+#[derive(Debug, Clone, Copy)]
+pub struct MultiplicativeHasher {
+    multiplier: u64,
+    table_size: u64,
+}
+
+// Any std hasher (e.g. FxHash or SipHash) reduced modulo the table size
+#[derive(Debug, Clone)]
+pub struct StdHasher<B: BuildHasher> {
+    build_hasher: B,
+    table_size: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BucketStatistics {
+    pub bucket_count: usize,
+    pub label_count: usize,
+    pub load_factor: f64,
+    pub longest_chain: usize,
+    // Labels beyond the first one in each bucket
+    pub collisions: usize,
+    // chain_length_histogram[n] holds how many buckets have n labels
+    pub chain_length_histogram: Vec<usize>,
+}
+
+impl MultiplicativeHasher {
+    pub fn new(multiplier: u64, table_size: u64) -> Self {
+        assert!(table_size > 0, "Table size must be positive");
+        // Only the multiplier's remainder matters, and keeping it below the table size means
+        // the products in `bucket` fit in u128
+        MultiplicativeHasher {
+            multiplier: multiplier % table_size,
+            table_size,
+        }
+    }
+
+    pub fn multiplier(&self) -> u64 {
+        self.multiplier
+    }
+
+    pub fn table_size(&self) -> u64 {
+        self.table_size
+    }
+}
+
+impl Default for MultiplicativeHasher {
+    fn default() -> Self {
+        MultiplicativeHasher::new(17, 256)
+    }
+}
+
+impl Hasher for MultiplicativeHasher {
+    fn bucket_count(&self) -> usize {
+        self.table_size as usize
+    }
+
+    fn bucket(&self, label: &str) -> usize {
+        let mut result: u128 = 0;
+        for c in label.bytes() {
+            result = (result + c as u128) * self.multiplier as u128 % self.table_size as u128;
+        }
+        result as usize
+    }
+}
+
+impl<B: BuildHasher> StdHasher<B> {
+    pub fn new(build_hasher: B, table_size: u64) -> Self {
+        assert!(table_size > 0, "Table size must be positive");
+        StdHasher {
+            build_hasher,
+            table_size,
+        }
+    }
+
+    pub fn table_size(&self) -> u64 {
+        self.table_size
+    }
+}
+
+impl StdHasher<FxBuildHasher> {
+    pub fn fx(table_size: u64) -> Self {
+        StdHasher::new(FxBuildHasher, table_size)
+    }
+}
+
+impl<B: BuildHasher> Hasher for StdHasher<B> {
+    fn bucket_count(&self) -> usize {
+        self.table_size as usize
+    }
+
+    fn bucket(&self, label: &str) -> usize {
+        (self.build_hasher.hash_one(label) % self.table_size) as usize
+    }
+}
+
+impl BucketStatistics {
+    pub fn new<'a>(hasher: &impl Hasher, labels: impl IntoIterator<Item = &'a str>) -> Self {
+        let labels: HashSet<&str> = labels.into_iter().collect();
+        let mut chain_lengths: Vec<usize> = vec![0; hasher.bucket_count()];
+        for label in labels.iter() {
+            chain_lengths[hasher.bucket(label)] += 1;
+        }
+
+        let longest_chain = chain_lengths.iter().copied().max().unwrap_or(0);
+        let mut chain_length_histogram: Vec<usize> = vec![0; longest_chain + 1];
+        for chain_length in chain_lengths.iter() {
+            chain_length_histogram[*chain_length] += 1;
+        }
+        let used_buckets = chain_lengths.iter().filter(|n| **n > 0).count();
+
+        BucketStatistics {
+            bucket_count: chain_lengths.len(),
+            label_count: labels.len(),
+            load_factor: labels.len() as f64 / chain_lengths.len() as f64,
+            longest_chain,
+            collisions: labels.len() - used_buckets,
+            chain_length_histogram,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BucketStatistics, Hasher, MultiplicativeHasher, StdHasher};
+
+    #[test]
+    fn defaults_to_the_puzzle_hash() {
+        let hasher = MultiplicativeHasher::default();
+
+        assert_eq!(hasher.bucket("HASH"), 52);
+        assert_eq!(hasher.bucket("rn"), 0);
+        assert_eq!(hasher.bucket("qp"), 1);
+    }
+
+    #[test]
+    fn hashes_with_multipliers_above_32_bits() {
+        let hasher = MultiplicativeHasher::new(1 << 50, 1000003);
+
+        assert_eq!(hasher.multiplier(), (1 << 50) % 1000003);
+        assert_eq!(hasher.bucket("HASH"), 311064);
+        assert_eq!(hasher.bucket("rn"), 688361);
+    }
+
+    #[test]
+    fn reduces_std_hashes_to_table_size() {
+        let hasher = StdHasher::fx(7);
+
+        assert!(["rn", "cm", "qp", "pc", "ot", "ab"]
+            .iter()
+            .all(|label| hasher.bucket(label) < 7));
+    }
+
+    #[test]
+    #[should_panic(expected = "Table size must be positive")]
+    fn rejects_empty_multiplicative_table() {
+        MultiplicativeHasher::new(17, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Table size must be positive")]
+    fn rejects_empty_std_table() {
+        StdHasher::fx(0);
+    }
+
+    #[test]
+    fn collects_bucket_statistics() {
+        let labels = "rn,cm,qp,cm,qp,pc,ot,ab,pc,pc,ot".split(",");
+
+        let statistics = BucketStatistics::new(&MultiplicativeHasher::default(), labels);
+
+        assert_eq!(
+            statistics,
+            BucketStatistics {
+                bucket_count: 256,
+                label_count: 6,
+                load_factor: 6.0 / 256.0,
+                longest_chain: 3,
+                collisions: 3,
+                chain_length_histogram: vec![253, 1, 1, 1],
+            }
+        );
+    }
+}
@@ -0,0 +1,354 @@
+pub mod hasher;
+pub mod reader;
+
+use std::{collections::HashMap, str::FromStr};
+
+use hasher::{Hasher, MultiplicativeHasher};
+
+#[derive(Debug)]
+pub struct Step {
+    pub label: String,
+    pub operation: Operation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lens {
+    pub label: String,
+    pub focal_length: u8,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug)]
+pub enum Operation {
+    ADD { focal_length: u8 },
+    REMOVE,
+}
+
+pub struct LensBoxes<H: Hasher = MultiplicativeHasher> {
+    hasher: H,
+    boxes: Vec<LensBox>,
+    focusing_power: u64,
+}
+
+// Lenses are kept in slots that are never shifted, so that a lens can be replaced or removed
+// in place. Removed lenses leave an empty slot behind until the box is compacted.
+#[derive(Default)]
+struct LensBox {
+    slots: Vec<Option<Lens>>,
+    slot_by_label: HashMap<String, usize>,
+    lens_counts: FenwickTree,
+    focal_lengths: FenwickTree,
+    // Sum of position * focal length over the lenses in this box
+    focusing_power: u64,
+}
+
+// Prefix sums over slots, supporting appends and point updates in O(log n)
+#[derive(Default)]
+struct FenwickTree {
+    tree: Vec<u64>,
+}
+
+impl LensBoxes {
+    pub fn new() -> LensBoxes {
+        LensBoxes::with_hasher(MultiplicativeHasher::default())
+    }
+}
+
+impl<H: Hasher> LensBoxes<H> {
+    pub fn with_hasher(hasher: H) -> LensBoxes<H> {
+        LensBoxes {
+            boxes: (0..hasher.bucket_count())
+                .map(|_| LensBox::default())
+                .collect(),
+            hasher,
+            focusing_power: 0,
+        }
+    }
+
+    pub fn box_index(&self, label: &str) -> usize {
+        self.hasher.bucket(label)
+    }
+
+    // Applies a step and returns the index of the box it affected
+    pub fn apply(&mut self, step: &Step) -> usize {
+        let i = self.box_index(&step.label);
+        let lens_box = &mut self.boxes[i];
+        let previous_power = lens_box.focusing_power;
+
+        match step.operation {
+            Operation::ADD { focal_length } => lens_box.add(Lens {
+                label: step.label.to_owned(),
+                focal_length,
+            }),
+            Operation::REMOVE => lens_box.remove(&step.label),
+        }
+
+        let box_number = i as u64 + 1;
+        self.focusing_power = overflow::sub(
+            self.focusing_power,
+            overflow::mul(box_number, previous_power, "box focusing power"),
+            "focusing power",
+        );
+        self.focusing_power = overflow::add(
+            self.focusing_power,
+            overflow::mul(box_number, lens_box.focusing_power, "box focusing power"),
+            "focusing power",
+        );
+        i
+    }
+
+    pub fn focusing_power(&self) -> u64 {
+        self.focusing_power
+    }
+
+    pub fn box_contents(&self, i: usize) -> Vec<&Lens> {
+        self.boxes[i].slots.iter().flatten().collect()
+    }
+
+    // Returns the box index and the (0-based) position within that box of the lens with a label
+    pub fn find(&self, label: &str) -> Option<(usize, usize)> {
+        let i = self.box_index(label);
+        let lens_box = &self.boxes[i];
+        let slot = *lens_box.slot_by_label.get(label)?;
+        Some((i, lens_box.lens_counts.prefix_sum(slot) as usize))
+    }
+
+    pub fn non_empty_boxes(&self) -> impl Iterator<Item = (usize, Vec<&Lens>)> {
+        (0..self.boxes.len())
+            .filter(|i| !self.boxes[*i].slot_by_label.is_empty())
+            .map(|i| (i, self.box_contents(i)))
+    }
+}
+
+impl Default for LensBoxes {
+    fn default() -> Self {
+        LensBoxes::new()
+    }
+}
+
+impl LensBox {
+    fn add(&mut self, lens: Lens) {
+        if let Some(&slot) = self.slot_by_label.get(&lens.label) {
+            let previous_focal_length = self.slots[slot].as_ref().unwrap().focal_length as u64;
+            let focal_length = lens.focal_length as u64;
+            let position = self.lens_counts.prefix_sum(slot + 1);
+            // Replacing a lens changes the power by its position times the change in focal length
+            self.focusing_power = overflow::sub(
+                self.focusing_power,
+                overflow::mul(position, previous_focal_length, "box focusing power"),
+                "box focusing power",
+            );
+            self.focusing_power = overflow::add(
+                self.focusing_power,
+                overflow::mul(position, focal_length, "box focusing power"),
+                "box focusing power",
+            );
+            self.focal_lengths
+                .add(slot, focal_length as i64 - previous_focal_length as i64);
+            self.slots[slot] = Some(lens);
+            return;
+        }
+
+        let position = self.slot_by_label.len() as u64 + 1;
+        self.focusing_power = overflow::add(
+            self.focusing_power,
+            overflow::mul(position, lens.focal_length as u64, "box focusing power"),
+            "box focusing power",
+        );
+        self.slot_by_label
+            .insert(lens.label.to_owned(), self.slots.len());
+        self.lens_counts.push(1);
+        self.focal_lengths.push(lens.focal_length as u64);
+        self.slots.push(Some(lens));
+    }
+
+    fn remove(&mut self, label: &str) {
+        let Some(slot) = self.slot_by_label.remove(label) else {
+            return;
+        };
+
+        // Every lens behind the removed one moves forward by one position
+        let lens = self.slots[slot].take().unwrap();
+        let position = self.lens_counts.prefix_sum(slot + 1);
+        let focal_lengths_behind = overflow::sub(
+            self.focal_lengths.total(),
+            self.focal_lengths.prefix_sum(slot + 1),
+            "focal lengths",
+        );
+        let removed_power = overflow::add(
+            overflow::mul(position, lens.focal_length as u64, "box focusing power"),
+            focal_lengths_behind,
+            "box focusing power",
+        );
+        self.focusing_power =
+            overflow::sub(self.focusing_power, removed_power, "box focusing power");
+        self.lens_counts.add(slot, -1);
+        self.focal_lengths.add(slot, -(lens.focal_length as i64));
+
+        if self.slots.len() > 2 * self.slot_by_label.len() {
+            self.compact();
+        }
+    }
+
+    fn compact(&mut self) {
+        let lenses: Vec<Lens> = self.slots.drain(..).flatten().collect();
+        *self = LensBox::default();
+        for lens in lenses {
+            self.add(lens);
+        }
+    }
+}
+
+impl FenwickTree {
+    fn push(&mut self, value: u64) {
+        let index = self.tree.len() + 1;
+        let lowest_bit = index & index.wrapping_neg();
+        let covered = overflow::sub(
+            self.prefix_sum(index - 1),
+            self.prefix_sum(index - lowest_bit),
+            "lens totals",
+        );
+        self.tree.push(overflow::add(value, covered, "lens totals"));
+    }
+
+    fn add(&mut self, slot: usize, delta: i64) {
+        let mut index = slot + 1;
+        while index <= self.tree.len() {
+            let value: i64 = overflow::convert(self.tree[index - 1], "lens totals");
+            self.tree[index - 1] =
+                overflow::convert(overflow::add(value, delta, "lens totals"), "lens totals");
+            index += index & index.wrapping_neg();
+        }
+    }
+
+    // Sum of the values in the first `count` slots
+    fn prefix_sum(&self, count: usize) -> u64 {
+        let mut index = count;
+        let mut sum = 0;
+        while index > 0 {
+            sum = overflow::add(sum, self.tree[index - 1], "lens totals");
+            index -= index & index.wrapping_neg();
+        }
+        sum
+    }
+
+    fn total(&self) -> u64 {
+        self.prefix_sum(self.tree.len())
+    }
+}
+
+impl FromStr for Step {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(label) = s.strip_suffix('-') {
+            if label.is_empty() {
+                return Err(());
+            }
+            return Ok(Step {
+                label: label.to_owned(),
+                operation: Operation::REMOVE,
+            });
+        }
+
+        let (label, focal_length) = s.split_once('=').ok_or(())?;
+        if label.is_empty() {
+            return Err(());
+        }
+        let focal_length: u8 = focal_length.parse().map_err(|_| ())?;
+        Ok(Step {
+            label: label.to_owned(),
+            operation: Operation::ADD { focal_length },
+        })
+    }
+}
+
+pub fn hash(s: &str) -> u64 {
+    MultiplicativeHasher::default().bucket(s) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        hasher::{Hasher, MultiplicativeHasher, StdHasher},
+        Lens, LensBoxes, Step,
+    };
+
+    const SAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    fn lens(label: &str, focal_length: u8) -> Lens {
+        Lens {
+            label: label.to_owned(),
+            focal_length,
+        }
+    }
+
+    fn recompute_focusing_power<H: Hasher>(boxes: &LensBoxes<H>) -> u64 {
+        boxes
+            .non_empty_boxes()
+            .map(|(i, lenses)| -> u64 {
+                lenses
+                    .iter()
+                    .enumerate()
+                    .map(|(j, lens)| (i as u64 + 1) * (j as u64 + 1) * lens.focal_length as u64)
+                    .sum()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn inspects_boxes() {
+        let mut boxes = LensBoxes::new();
+        for step in SAMPLE.split(",") {
+            boxes.apply(&step.parse().unwrap());
+        }
+
+        assert_eq!(boxes.box_contents(0), vec![&lens("rn", 1), &lens("cm", 2)]);
+        assert_eq!(
+            boxes.box_contents(3),
+            vec![&lens("ot", 7), &lens("ab", 5), &lens("pc", 6)]
+        );
+        assert_eq!(boxes.find("pc"), Some((3, 2)));
+        assert_eq!(boxes.find("qp"), None);
+        assert_eq!(boxes.focusing_power(), 145);
+    }
+
+    #[test]
+    fn keeps_focusing_power_up_to_date() {
+        let mut boxes = LensBoxes::new();
+        let labels = ["rn", "cm", "qp", "pc", "ot", "ab", "cma", "xb", "qpp"];
+
+        for n in 0..2000 {
+            let label = labels[(n * 7 + n / 3) % labels.len()];
+            let step: Step = if n % 3 == 0 {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", n % 9 + 1)
+            }
+            .parse()
+            .unwrap();
+            boxes.apply(&step);
+
+            assert_eq!(boxes.focusing_power(), recompute_focusing_power(&boxes));
+        }
+    }
+
+    #[test]
+    fn runs_with_other_hashers() {
+        let mut small_table = LensBoxes::with_hasher(MultiplicativeHasher::new(31, 4));
+        let mut fx = LensBoxes::with_hasher(StdHasher::fx(1024));
+
+        for step in SAMPLE.split(",") {
+            small_table.apply(&step.parse().unwrap());
+            fx.apply(&step.parse().unwrap());
+        }
+
+        assert!(small_table.non_empty_boxes().all(|(i, _)| i < 4));
+        assert_eq!(
+            small_table.focusing_power(),
+            recompute_focusing_power(&small_table)
+        );
+        assert_eq!(fx.focusing_power(), recompute_focusing_power(&fx));
+        assert_eq!(fx.find("pc").map(|(i, _)| i), Some(fx.box_index("pc")));
+    }
+}
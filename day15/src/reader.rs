@@ -0,0 +1,180 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+};
+
+use crate::Step;
+
+// Reads comma separated steps one at a time, ignoring newlines, so that the whole
+// initialization sequence never needs to be in memory
+pub struct RawStepReader<R: BufRead> {
+    reader: R,
+    buffer: Vec<u8>,
+    position: u64,
+    finished: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RawStep {
+    // Byte offset of the first character of the step in the input
+    pub offset: u64,
+    pub text: String,
+}
+
+pub struct StepReader<R: BufRead> {
+    raw_steps: RawStepReader<R>,
+}
+
+#[derive(Debug)]
+pub enum ReadStepError {
+    Io(io::Error),
+    Malformed { offset: u64, step: String },
+}
+
+impl<R: BufRead> RawStepReader<R> {
+    pub fn new(reader: R) -> Self {
+        RawStepReader {
+            reader,
+            buffer: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for RawStepReader<R> {
+    type Item = io::Result<RawStep>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        self.buffer.clear();
+        let read = match self.reader.read_until(b',', &mut self.buffer) {
+            Ok(read) => read,
+            Err(error) => {
+                self.finished = true;
+                return Some(Err(error));
+            }
+        };
+
+        let mut offset = self.position;
+        self.position += read as u64;
+        if self.buffer.last() == Some(&b',') {
+            self.buffer.pop();
+        } else {
+            self.finished = true;
+        }
+
+        // Newlines before the step don't count towards its offset
+        let leading_newlines = self
+            .buffer
+            .iter()
+            .take_while(|c| **c == b'\n' || **c == b'\r')
+            .count();
+        offset += leading_newlines as u64;
+        self.buffer.retain(|c| *c != b'\n' && *c != b'\r');
+
+        if self.finished && self.buffer.is_empty() {
+            return None;
+        }
+
+        Some(Ok(RawStep {
+            offset,
+            text: String::from_utf8_lossy(&self.buffer).into_owned(),
+        }))
+    }
+}
+
+impl<R: BufRead> StepReader<R> {
+    pub fn new(reader: R) -> Self {
+        StepReader {
+            raw_steps: RawStepReader::new(reader),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for StepReader<R> {
+    type Item = Result<Step, ReadStepError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let raw_step = match self.raw_steps.next()? {
+            Ok(raw_step) => raw_step,
+            Err(error) => return Some(Err(ReadStepError::Io(error))),
+        };
+
+        Some(raw_step.text.parse().map_err(|_| ReadStepError::Malformed {
+            offset: raw_step.offset,
+            step: raw_step.text,
+        }))
+    }
+}
+
+impl fmt::Display for ReadStepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadStepError::Io(error) => write!(f, "Failed to read steps: {error}"),
+            ReadStepError::Malformed { offset, step } => {
+                write!(f, "Malformed step '{step}' at byte {offset}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RawStep, RawStepReader, ReadStepError, StepReader};
+
+    #[test]
+    fn ignores_newlines() {
+        let input = "rn=1,cm-\n,qp\n=3,\ncm=2\n";
+
+        let steps: Vec<RawStep> = RawStepReader::new(input.as_bytes())
+            .map(|step| step.unwrap())
+            .collect();
+
+        assert_eq!(
+            steps,
+            vec![
+                RawStep {
+                    offset: 0,
+                    text: "rn=1".to_owned(),
+                },
+                RawStep {
+                    offset: 5,
+                    text: "cm-".to_owned(),
+                },
+                RawStep {
+                    offset: 10,
+                    text: "qp=3".to_owned(),
+                },
+                RawStep {
+                    offset: 17,
+                    text: "cm=2".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_steps_with_their_offset() {
+        let input = "rn=1,cm,qp=300,=2,ot=7";
+
+        let errors: Vec<(u64, String)> = StepReader::new(input.as_bytes())
+            .filter_map(|step| match step {
+                Err(ReadStepError::Malformed { offset, step }) => Some((offset, step)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                (5, "cm".to_owned()),
+                (8, "qp=300".to_owned()),
+                (15, "=2".to_owned()),
+            ]
+        );
+    }
+}
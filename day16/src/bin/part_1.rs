@@ -35,7 +35,7 @@ enum Direction {
 fn solve(input: &str) -> u64 {
     let mut grid: Grid = input.parse().unwrap();
     grid.shine_beam();
-    grid.energised_tiles.len() as u64
+    overflow::convert(grid.energised_tiles.len(), "energised tiles")
 }
 
 impl Grid {
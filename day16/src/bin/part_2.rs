@@ -45,7 +45,10 @@ fn solve(input: &str) -> u64 {
             direction,
         });
         grid.shine_beam();
-        max_energised = max(max_energised, grid.energised_tiles.len() as u64);
+        max_energised = max(
+            max_energised,
+            overflow::convert(grid.energised_tiles.len(), "energised tiles"),
+        );
     };
 
     for i in 0..n_rows {
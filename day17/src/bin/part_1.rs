@@ -145,7 +145,11 @@ impl Solver {
 
         Some(PathNode {
             previous: Some(Rc::new(node.clone())),
-            total_heat_loss: node.total_heat_loss + self.city.get(&next_position) as u64,
+            total_heat_loss: overflow::add(
+                node.total_heat_loss,
+                self.city.get(&next_position) as u64,
+                "total heat loss",
+            ),
             position: next_position,
             direction: next_direction,
             consecutive_staight_moves: 1,
@@ -167,7 +171,11 @@ impl Solver {
 
         Some(PathNode {
             previous: Some(Rc::new(node.clone())),
-            total_heat_loss: node.total_heat_loss + self.city.get(&next_position) as u64,
+            total_heat_loss: overflow::add(
+                node.total_heat_loss,
+                self.city.get(&next_position) as u64,
+                "total heat loss",
+            ),
             position: next_position,
             direction: next_direction,
             consecutive_staight_moves: 1,
@@ -193,7 +201,11 @@ impl Solver {
 
         Some(PathNode {
             previous: Some(Rc::new(node.clone())),
-            total_heat_loss: node.total_heat_loss + self.city.get(&next_position) as u64,
+            total_heat_loss: overflow::add(
+                node.total_heat_loss,
+                self.city.get(&next_position) as u64,
+                "total heat loss",
+            ),
             position: next_position,
             direction: node.direction.clone(),
             consecutive_staight_moves: node.consecutive_staight_moves + 1,
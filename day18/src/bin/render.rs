@@ -0,0 +1,67 @@
+use std::{env, fs::File, io::BufWriter, process};
+
+use day18::{Encoding, Polygon};
+
+const USAGE: &str = "Usage: render <output.png|output.ppm> [--hex] [--max-size N]";
+
+struct Options {
+    path: String,
+    encoding: Encoding,
+    max_size: usize,
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}");
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let input = include_str!("../../input.txt");
+    let polygon: Polygon = input
+        .parse()
+        .unwrap_or_else(|line| panic!("Invalid dig instruction on line {line}"));
+    let raster = polygon.render(options.encoding, options.max_size);
+
+    let writer = BufWriter::new(File::create(&options.path).unwrap());
+    if options.path.ends_with(".png") {
+        raster.write_png(writer).unwrap();
+    } else {
+        raster.write_ppm(writer).unwrap();
+    }
+    println!(
+        "{}x{} pixels, {} meters per pixel",
+        raster.width, raster.height, raster.scale
+    );
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut path: Option<String> = None;
+    let mut encoding = Encoding::Plain;
+    let mut max_size: usize = 1000;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--hex" => encoding = Encoding::Hex,
+            "--max-size" => {
+                let value = args.next().ok_or("Missing value for --max-size")?;
+                max_size = match value.parse() {
+                    Ok(size) if size > 0 => size,
+                    _ => return Err(format!("Invalid maximum size '{value}'")),
+                };
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("Unexpected argument '{arg}'")),
+        }
+    }
+
+    Ok(Options {
+        path: path.ok_or("Missing output file")?,
+        encoding,
+        max_size,
+    })
+}
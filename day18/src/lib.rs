@@ -0,0 +1,208 @@
+use std::str::FromStr;
+
+use rectilinear::RectilinearPolygon;
+
+pub mod render;
+pub mod validation;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Point(pub i128, pub i128);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    R,
+    D,
+    L,
+    U,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub meters: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+// Which way to read each line of the dig plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    // Part 1: `R 6`
+    Plain,
+    // Part 2: the colour field, where the last hex digit is the direction and the others
+    // are the meters
+    Hex,
+}
+
+// One line of the dig plan, with both of its readings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigInstruction {
+    pub plain: Instruction,
+    pub hex: Instruction,
+    pub colour: Colour,
+}
+
+#[derive(Debug)]
+pub struct Polygon {
+    pub instructions: Vec<DigInstruction>,
+}
+
+impl DigInstruction {
+    pub fn decode(&self, encoding: Encoding) -> &Instruction {
+        match encoding {
+            Encoding::Plain => &self.plain,
+            Encoding::Hex => &self.hex,
+        }
+    }
+}
+
+impl Polygon {
+    pub fn vertices(&self, encoding: Encoding) -> Vec<Point> {
+        let mut position = Point(0, 0);
+        let mut vertices = vec![position];
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let _line = overflow::on_line(index + 1);
+            position = position.step(instruction.decode(encoding));
+            vertices.push(position);
+        }
+
+        vertices
+    }
+
+    // Cubic meters of lava the trench and its interior hold
+    pub fn calculate_area(&self, encoding: Encoding) -> u128 {
+        self.to_rectilinear(encoding).lattice_points()
+    }
+
+    pub fn to_rectilinear(&self, encoding: Encoding) -> RectilinearPolygon {
+        let vertices = self.vertices(encoding).iter().map(|p| (p.0, p.1)).collect();
+        RectilinearPolygon::new(vertices)
+    }
+}
+
+impl Point {
+    pub fn step(&self, instruction: &Instruction) -> Point {
+        let Point(i, j) = *self;
+        let meters: i128 = overflow::convert(instruction.meters, "meters");
+
+        match instruction.direction {
+            Direction::R => Point(i, overflow::add(j, meters, "column")),
+            Direction::D => Point(overflow::add(i, meters, "row"), j),
+            Direction::L => Point(i, overflow::sub(j, meters, "column")),
+            Direction::U => Point(overflow::sub(i, meters, "row"), j),
+        }
+    }
+}
+
+// Fails with the 1-based number of the first line that is not a dig instruction
+impl FromStr for Polygon {
+    type Err = usize;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let instructions = s
+            .lines()
+            .enumerate()
+            .map(|(index, line)| line.parse().map_err(|_| index + 1))
+            .collect::<Result<_, _>>()?;
+        Ok(Polygon { instructions })
+    }
+}
+
+impl FromStr for DigInstruction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let [direction, meters, colour] = parts[..] else {
+            return Err(());
+        };
+        let plain = Instruction {
+            direction: direction.parse()?,
+            meters: meters.parse().map_err(|_| ())?,
+        };
+
+        let hex_digits = colour
+            .strip_prefix("(#")
+            .and_then(|c| c.strip_suffix(')'))
+            .ok_or(())?;
+        if hex_digits.len() != 6 || !hex_digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(());
+        }
+        let channel = |k: usize| u8::from_str_radix(&hex_digits[2 * k..2 * k + 2], 16).unwrap();
+        let colour = Colour(channel(0), channel(1), channel(2));
+
+        let direction = match &hex_digits[5..] {
+            "0" => Direction::R,
+            "1" => Direction::D,
+            "2" => Direction::L,
+            "3" => Direction::U,
+            _ => return Err(()),
+        };
+        let meters = u128::from_str_radix(&hex_digits[..5], 16).unwrap();
+        let hex = Instruction { direction, meters };
+
+        Ok(DigInstruction { plain, hex, colour })
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "R" => Ok(Direction::R),
+            "D" => Ok(Direction::D),
+            "L" => Ok(Direction::L),
+            "U" => Ok(Direction::U),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Colour, DigInstruction, Direction, Instruction, Polygon};
+    use test_case::test_case;
+
+    #[test]
+    fn decodes_both_encodings_and_colour() {
+        let instruction: DigInstruction = "R 6 (#70c710)".parse().unwrap();
+
+        assert_eq!(
+            instruction,
+            DigInstruction {
+                plain: Instruction {
+                    direction: Direction::R,
+                    meters: 6,
+                },
+                hex: Instruction {
+                    direction: Direction::R,
+                    meters: 461937,
+                },
+                colour: Colour(0x70, 0xc7, 0x10),
+            }
+        );
+    }
+
+    #[test_case("X 6 (#70c710)")]
+    #[test_case("R six (#70c710)")]
+    #[test_case("R 6 (#70c714)")]
+    #[test_case("R 6 (#70c71)")]
+    #[test_case("R 6 (#70g710)")]
+    #[test_case("R 6 #70c710")]
+    #[test_case("R 6")]
+    fn rejects_malformed_instructions(line: &str) {
+        assert_eq!(line.parse::<DigInstruction>(), Err(()));
+    }
+
+    #[test]
+    fn reports_line_of_malformed_instruction() {
+        let error = "R 6 (#70c710)\nD 5 (#0dc571)\nL 2\n"
+            .parse::<Polygon>()
+            .unwrap_err();
+
+        assert_eq!(error, 3);
+    }
+}
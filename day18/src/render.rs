@@ -0,0 +1,196 @@
+use std::io::{self, Write};
+
+use crate::{Colour, Encoding, Point, Polygon};
+
+const BACKGROUND: Colour = Colour(0xff, 0xff, 0xff);
+const INTERIOR: Colour = Colour(0xc8, 0xc8, 0xc8);
+
+#[derive(Debug)]
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    // Meters of the dig plan covered by each pixel along either axis
+    pub scale: u128,
+    pixels: Vec<Colour>,
+}
+
+impl Raster {
+    pub fn pixel(&self, row: usize, column: usize) -> Colour {
+        self.pixels[row * self.width + column]
+    }
+
+    fn set_pixel(&mut self, row: usize, column: usize, colour: Colour) {
+        self.pixels[row * self.width + column] = colour;
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|Colour(r, g, b)| [*r, *g, *b])
+            .collect()
+    }
+
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.bytes())
+    }
+
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.bytes())?;
+        Ok(())
+    }
+}
+
+impl Polygon {
+    // Draws the trench with the colour of each segment over its filled interior. Plans wider
+    // or taller than max_size meters are scaled down to fit.
+    pub fn render(&self, encoding: Encoding, max_size: usize) -> Raster {
+        assert!(max_size > 0, "Maximum size must be positive");
+        let vertices = self.vertices(encoding);
+        let min_row = vertices.iter().map(|p| p.0).min().unwrap();
+        let max_row = vertices.iter().map(|p| p.0).max().unwrap();
+        let min_column = vertices.iter().map(|p| p.1).min().unwrap();
+        let max_column = vertices.iter().map(|p| p.1).max().unwrap();
+        let extent = max_row
+            .abs_diff(min_row)
+            .max(max_column.abs_diff(min_column))
+            + 1;
+        let scale = extent.div_ceil(max_size as u128).max(1);
+
+        let to_pixel = |Point(i, j): Point| -> (usize, usize) {
+            (
+                (i.abs_diff(min_row) / scale) as usize,
+                (j.abs_diff(min_column) / scale) as usize,
+            )
+        };
+        let (last_row, last_column) = to_pixel(Point(max_row, max_column));
+        let mut raster = Raster {
+            width: last_column + 1,
+            height: last_row + 1,
+            scale,
+            pixels: vec![BACKGROUND; (last_row + 1) * (last_column + 1)],
+        };
+
+        // Scanline fill along the top of every pixel row. Vertical edges cover the rows from
+        // their upper end up to, but excluding, their lower end, so that a row passing through
+        // a horizontal edge counts each crossing once.
+        let mut vertical_edges: Vec<(i128, i128, i128)> = vertices
+            .windows(2)
+            .filter(|pair| pair[0].1 == pair[1].1 && pair[0].0 != pair[1].0)
+            .map(|pair| {
+                (
+                    pair[0].0.min(pair[1].0),
+                    pair[0].0.max(pair[1].0),
+                    pair[0].1,
+                )
+            })
+            .collect();
+        vertical_edges.sort_unstable_by_key(|(_, _, column)| *column);
+        for row in 0..raster.height {
+            let i = min_row + (row as u128 * scale) as i128;
+            let crossings: Vec<i128> = vertical_edges
+                .iter()
+                .filter(|(top, bottom, _)| *top <= i && i < *bottom)
+                .map(|(_, _, column)| *column)
+                .collect();
+            for pair in crossings.chunks_exact(2) {
+                let (_, start) = to_pixel(Point(i, pair[0]));
+                let (_, end) = to_pixel(Point(i, pair[1]));
+                for column in start..=end {
+                    raster.set_pixel(row, column, INTERIOR);
+                }
+            }
+        }
+
+        for (pair, instruction) in vertices.windows(2).zip(&self.instructions) {
+            let (start_row, start_column) = to_pixel(pair[0]);
+            let (end_row, end_column) = to_pixel(pair[1]);
+            for row in start_row.min(end_row)..=start_row.max(end_row) {
+                for column in start_column.min(end_column)..=start_column.max(end_column) {
+                    raster.set_pixel(row, column, instruction.colour);
+                }
+            }
+        }
+
+        raster
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BACKGROUND, INTERIOR};
+    use crate::{Colour, Encoding, Polygon};
+    use indoc::indoc;
+
+    const SAMPLE: &str = indoc! {"
+        R 6 (#70c710)
+        D 5 (#0dc571)
+        L 2 (#5713f0)
+        D 2 (#d2c081)
+        R 2 (#59c680)
+        D 2 (#411b91)
+        L 5 (#8ceee2)
+        U 2 (#caa173)
+        L 1 (#1b58a2)
+        U 2 (#caa171)
+        R 2 (#7807d2)
+        U 3 (#a77fa3)
+        L 2 (#015232)
+        U 2 (#7a21e3)
+    "};
+
+    #[test]
+    fn renders_trench_and_interior() {
+        let polygon: Polygon = SAMPLE.parse().unwrap();
+
+        let raster = polygon.render(Encoding::Plain, 100);
+
+        assert_eq!((raster.width, raster.height, raster.scale), (7, 10, 1));
+        assert_eq!(raster.pixel(0, 3), Colour(0x70, 0xc7, 0x10));
+        assert_eq!(raster.pixel(3, 6), Colour(0x0d, 0xc5, 0x71));
+        assert_eq!(raster.pixel(3, 0), BACKGROUND);
+        assert_eq!(raster.pixel(6, 3), INTERIOR);
+        assert_eq!(raster.pixel(6, 5), BACKGROUND);
+        let dug = (0..raster.height)
+            .flat_map(|row| (0..raster.width).map(move |column| (row, column)))
+            .filter(|(row, column)| raster.pixel(*row, *column) != BACKGROUND)
+            .count();
+        assert_eq!(dug, 62);
+    }
+
+    #[test]
+    fn scales_down_large_plans() {
+        let polygon: Polygon = SAMPLE.parse().unwrap();
+
+        let raster = polygon.render(Encoding::Hex, 100);
+
+        assert!(raster.width <= 100 && raster.height <= 100);
+        assert!(raster.scale > 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum size must be positive")]
+    fn rejects_zero_max_size() {
+        let polygon: Polygon = SAMPLE.parse().unwrap();
+
+        polygon.render(Encoding::Plain, 0);
+    }
+
+    #[test]
+    fn writes_ppm() {
+        let polygon: Polygon = "R 1 (#ff0000)\nD 1 (#00ff00)\nL 1 (#0000f0)\nU 1 (#000000)\n"
+            .parse()
+            .unwrap();
+        let raster = polygon.render(Encoding::Plain, 100);
+        let mut ppm: Vec<u8> = Vec::new();
+
+        raster.write_ppm(&mut ppm).unwrap();
+
+        assert_eq!(ppm[..11], *b"P6\n2 2\n255\n");
+        assert_eq!(ppm[11..], [0, 0, 0, 0, 255, 0, 0, 0, 0, 0, 0, 240]);
+    }
+}
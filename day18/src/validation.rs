@@ -0,0 +1,229 @@
+use std::fmt;
+
+use crate::{Encoding, Point, Polygon};
+
+// A reason for the shoelace formula not to apply, pointing at a 0-based instruction index
+#[derive(Debug, PartialEq, Eq)]
+pub enum PlanError {
+    ZeroLengthMove { instruction: usize },
+    // The last instruction does not lead back to the start
+    Unclosed { instruction: usize, end: Point },
+    // The trench dug by the instruction crosses or runs along the one dug by an earlier one
+    SelfIntersection { instruction: usize, other: usize },
+    // No instruction moves, so there is no trench to enclose anything
+    EnclosesNoArea,
+}
+
+struct Segment {
+    instruction: usize,
+    start: Point,
+    end: Point,
+}
+
+impl Segment {
+    fn rows(&self) -> (i128, i128) {
+        (self.start.0.min(self.end.0), self.start.0.max(self.end.0))
+    }
+
+    fn columns(&self) -> (i128, i128) {
+        (self.start.1.min(self.end.1), self.start.1.max(self.end.1))
+    }
+
+    // Axis-aligned segments touch exactly when their bounding boxes do
+    fn touches(&self, other: &Segment) -> bool {
+        let overlap = |a: (i128, i128), b: (i128, i128)| a.0 <= b.1 && b.0 <= a.1;
+        overlap(self.rows(), other.rows()) && overlap(self.columns(), other.columns())
+    }
+
+    // Consecutive segments always share a corner, so they only overlap when the second one
+    // turns back over the first
+    fn turns_back_over(&self, next: &Segment) -> bool {
+        let direction = |segment: &Segment| {
+            (
+                (segment.end.0 - segment.start.0).signum(),
+                (segment.end.1 - segment.start.1).signum(),
+            )
+        };
+        let (a, b) = (direction(self), direction(next));
+        a.0 == -b.0 && a.1 == -b.1
+    }
+}
+
+impl Polygon {
+    pub fn validate(&self, encoding: Encoding) -> Result<(), Vec<PlanError>> {
+        let vertices = self.vertices(encoding);
+        let mut errors: Vec<PlanError> = Vec::new();
+
+        let mut segments: Vec<Segment> = Vec::new();
+        for (instruction, pair) in vertices.windows(2).enumerate() {
+            if pair[0] == pair[1] {
+                errors.push(PlanError::ZeroLengthMove { instruction });
+            } else {
+                segments.push(Segment {
+                    instruction,
+                    start: pair[0],
+                    end: pair[1],
+                });
+            }
+        }
+
+        let start = vertices[0];
+        let end = vertices[vertices.len() - 1];
+        let is_closed = start == end;
+        if !is_closed {
+            errors.push(PlanError::Unclosed {
+                instruction: vertices.len() - 2,
+                end,
+            });
+        }
+
+        for (j, segment) in segments.iter().enumerate() {
+            for (i, other) in segments[..j].iter().enumerate() {
+                let intersects = if i + 1 == j {
+                    other.turns_back_over(segment)
+                } else if i == 0 && j == segments.len() - 1 && is_closed {
+                    // The loop closes at the start, where the last segment meets the first
+                    segment.turns_back_over(other)
+                } else {
+                    segment.touches(other)
+                };
+                if intersects {
+                    errors.push(PlanError::SelfIntersection {
+                        instruction: segment.instruction,
+                        other: other.instruction,
+                    });
+                }
+            }
+        }
+
+        errors.sort_by_key(|error| match error {
+            PlanError::ZeroLengthMove { instruction }
+            | PlanError::Unclosed { instruction, .. }
+            | PlanError::SelfIntersection { instruction, .. } => *instruction,
+            PlanError::EnclosesNoArea => 0,
+        });
+        // Any closed loop with a move that neither crosses nor retraces itself encloses some area
+        if segments.is_empty() {
+            errors.push(PlanError::EnclosesNoArea);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // The dug volume, once the plan is known to describe a simple closed loop
+    pub fn checked_area(&self, encoding: Encoding) -> Result<u128, Vec<PlanError>> {
+        self.validate(encoding)?;
+        Ok(self.calculate_area(encoding))
+    }
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::ZeroLengthMove { instruction } => {
+                write!(f, "Instruction {instruction} does not move")
+            }
+            PlanError::Unclosed {
+                instruction,
+                end: Point(i, j),
+            } => write!(
+                f,
+                "Instruction {instruction} ends at ({i}, {j}) instead of the start"
+            ),
+            PlanError::SelfIntersection { instruction, other } => {
+                write!(f, "Instruction {instruction} crosses instruction {other}")
+            }
+            PlanError::EnclosesNoArea => write!(f, "The plan encloses no area"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PlanError;
+    use crate::{Encoding, Point, Polygon};
+    use test_case::test_case;
+
+    #[test_case("R 2 (#000020)\nD 2 (#000021)\nL 2 (#000022)\nU 2 (#000023)")]
+    #[test_case("R 1 (#000010)\nR 1 (#000010)\nD 2 (#000021)\nL 2 (#000022)\nU 2 (#000023)")]
+    fn accepts_simple_loops(plan: &str) {
+        let polygon: Polygon = plan.parse().unwrap();
+
+        assert_eq!(polygon.validate(Encoding::Plain), Ok(()));
+        assert_eq!(polygon.checked_area(Encoding::Plain), Ok(9));
+    }
+
+    #[test]
+    fn reports_zero_length_moves() {
+        let polygon: Polygon =
+            "R 2 (#000020)\nD 0 (#000001)\nD 2 (#000021)\nL 2 (#000022)\nU 2 (#000023)"
+                .parse()
+                .unwrap();
+
+        let errors = polygon.validate(Encoding::Plain).unwrap_err();
+
+        assert_eq!(errors, vec![PlanError::ZeroLengthMove { instruction: 1 }]);
+    }
+
+    #[test]
+    fn reports_unclosed_loops() {
+        let polygon: Polygon = "R 2 (#000020)\nD 2 (#000021)\nL 2 (#000022)\nU 1 (#000013)"
+            .parse()
+            .unwrap();
+
+        let errors = polygon.checked_area(Encoding::Plain).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![PlanError::Unclosed {
+                instruction: 3,
+                end: Point(1, 0),
+            }]
+        );
+    }
+
+    #[test_case(""; "empty plan")]
+    #[test_case("R 0 (#000000)"; "only standing still")]
+    fn reports_plans_enclosing_no_area(plan: &str) {
+        let polygon: Polygon = plan.parse().unwrap();
+
+        let errors = polygon.checked_area(Encoding::Plain).unwrap_err();
+
+        assert_eq!(errors.last(), Some(&PlanError::EnclosesNoArea));
+        assert_eq!(
+            errors.last().unwrap().to_string(),
+            "The plan encloses no area"
+        );
+    }
+
+    #[test]
+    fn reports_crossings() {
+        let polygon: Polygon =
+            "R 2 (#000020)\nD 2 (#000021)\nL 1 (#000012)\nU 3 (#000033)\nL 1 (#000012)\nD 1 (#000011)"
+                .parse()
+                .unwrap();
+
+        let errors = polygon.validate(Encoding::Plain).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![PlanError::SelfIntersection {
+                instruction: 3,
+                other: 0,
+            }]
+        );
+    }
+
+    #[test_case("R 2 (#000020)\nL 1 (#000012)\nD 1 (#000011)\nL 1 (#000012)\nU 1 (#000013)", 1, 0; "turning back")]
+    #[test_case("R 2 (#000020)\nD 2 (#000021)\nL 2 (#000022)\nU 2 (#000023)\nR 1 (#000010)\nL 1 (#000012)", 4, 0; "retracing the first move")]
+    fn reports_overlaps(plan: &str, instruction: usize, other: usize) {
+        let polygon: Polygon = plan.parse().unwrap();
+
+        let errors = polygon.validate(Encoding::Plain).unwrap_err();
+
+        assert!(errors.contains(&PlanError::SelfIntersection { instruction, other }));
+    }
+}
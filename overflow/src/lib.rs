@@ -0,0 +1,207 @@
+// Arithmetic for the solvers. With the `checked` feature, every operation is checked and an
+// overflow panics naming the calculation and the input line being solved. Without it, these
+// are the plain operators.
+#[cfg(feature = "checked")]
+use std::cell::Cell;
+
+use num_traits::{AsPrimitive, CheckedAdd, CheckedMul, CheckedSub, One, PrimInt, Zero};
+
+#[cfg(feature = "checked")]
+thread_local! {
+    static LINE: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+// Marks the 1-based input line being solved until dropped
+#[must_use]
+pub struct LineScope {
+    #[cfg(feature = "checked")]
+    previous: Option<usize>,
+}
+
+pub fn on_line(line: usize) -> LineScope {
+    #[cfg(feature = "checked")]
+    {
+        LineScope {
+            previous: LINE.with(|current| current.replace(Some(line))),
+        }
+    }
+    #[cfg(not(feature = "checked"))]
+    {
+        let _ = line;
+        LineScope {}
+    }
+}
+
+impl Drop for LineScope {
+    fn drop(&mut self) {
+        #[cfg(feature = "checked")]
+        LINE.with(|current| current.set(self.previous));
+    }
+}
+
+#[cfg(feature = "checked")]
+fn report(calculation: &str) -> ! {
+    match LINE.with(|current| current.get()) {
+        Some(line) => panic!("Overflow in {calculation} on line {line}"),
+        None => panic!("Overflow in {calculation}"),
+    }
+}
+
+pub fn add<T: CheckedAdd>(a: T, b: T, calculation: &str) -> T {
+    #[cfg(feature = "checked")]
+    {
+        a.checked_add(&b).unwrap_or_else(|| report(calculation))
+    }
+    #[cfg(not(feature = "checked"))]
+    {
+        let _ = calculation;
+        a + b
+    }
+}
+
+pub fn sub<T: CheckedSub>(a: T, b: T, calculation: &str) -> T {
+    #[cfg(feature = "checked")]
+    {
+        a.checked_sub(&b).unwrap_or_else(|| report(calculation))
+    }
+    #[cfg(not(feature = "checked"))]
+    {
+        let _ = calculation;
+        a - b
+    }
+}
+
+pub fn mul<T: CheckedMul>(a: T, b: T, calculation: &str) -> T {
+    #[cfg(feature = "checked")]
+    {
+        a.checked_mul(&b).unwrap_or_else(|| report(calculation))
+    }
+    #[cfg(not(feature = "checked"))]
+    {
+        let _ = calculation;
+        a * b
+    }
+}
+
+pub fn pow<T: PrimInt>(base: T, exponent: u32, calculation: &str) -> T {
+    #[cfg(feature = "checked")]
+    {
+        num_traits::checked_pow(base, exponent as usize).unwrap_or_else(|| report(calculation))
+    }
+    #[cfg(not(feature = "checked"))]
+    {
+        let _ = calculation;
+        base.pow(exponent)
+    }
+}
+
+pub fn sum<T: CheckedAdd + Zero, I: IntoIterator<Item = T>>(values: I, calculation: &str) -> T {
+    values
+        .into_iter()
+        .fold(T::zero(), |total, value| add(total, value, calculation))
+}
+
+pub fn product<T: CheckedMul + One, I: IntoIterator<Item = T>>(values: I, calculation: &str) -> T {
+    values
+        .into_iter()
+        .fold(T::one(), |total, value| mul(total, value, calculation))
+}
+
+// Sums a value computed from every line of the input, each within its own line scope
+pub fn sum_by_line<'a, T, F>(input: &'a str, calculation: &str, mut value: F) -> T
+where
+    T: CheckedAdd + Zero,
+    F: FnMut(&'a str) -> T,
+{
+    let mut total = T::zero();
+    for (index, line) in input.lines().enumerate() {
+        let _line = on_line(index + 1);
+        total = add(total, value(line), calculation);
+    }
+    total
+}
+
+// Converts between integer types, where `as` would silently truncate or change sign
+pub fn convert<T, U>(value: T, calculation: &str) -> U
+where
+    T: AsPrimitive<U>,
+    U: TryFrom<T> + Copy + 'static,
+{
+    #[cfg(feature = "checked")]
+    {
+        U::try_from(value).unwrap_or_else(|_| report(calculation))
+    }
+    #[cfg(not(feature = "checked"))]
+    {
+        let _ = calculation;
+        value.as_()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{add, convert, mul, pow, product, sub, sum, sum_by_line};
+
+    #[cfg(feature = "checked")]
+    use super::on_line;
+
+    #[test]
+    fn calculates_without_overflow() {
+        assert_eq!(add(2u32, 3, "sum"), 5);
+        assert_eq!(sub(3i64, 5, "difference"), -2);
+        assert_eq!(mul(4u8, 5, "product"), 20);
+        assert_eq!(pow(2u64, 40, "power"), 1 << 40);
+        assert_eq!(sum(vec![1u16, 2, 3], "sum"), 6);
+        assert_eq!(product(vec![2u16, 3, 4], "product"), 24);
+        assert_eq!(convert::<i64, u32>(7, "conversion"), 7);
+        assert_eq!(sum_by_line("1\n2\n3", "sum", |line| line.len()), 3);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "Overflow in total winnings on line 3")]
+    fn reports_calculation_and_line() {
+        let _line = on_line(3);
+        mul(u32::MAX, 2, "total winnings");
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "Overflow in location on line 2")]
+    fn reports_innermost_line() {
+        let _outer = on_line(1);
+        {
+            let _inner = on_line(2);
+            sum(vec![u64::MAX, 1], "location");
+        }
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "Overflow in calibration values on line 2")]
+    fn reports_line_of_summed_value() {
+        sum_by_line("1\n255\n3", "calibration values", |line| {
+            line.parse::<u8>().unwrap()
+        });
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "Overflow in seed")]
+    fn reports_lossy_conversions() {
+        convert::<i64, u64>(-1, "seed");
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn forgets_lines_once_solved() {
+        {
+            let _line = on_line(5);
+        }
+
+        let result = std::panic::catch_unwind(|| sub(0u8, 1, "count"));
+
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(message, "Overflow in count");
+    }
+}
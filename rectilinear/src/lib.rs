@@ -0,0 +1,218 @@
+// Closed polygons on the integer lattice whose edges are all horizontal or vertical, such as a
+// loop of pipes or a dug trench. Points are (row, column), with rows growing downwards.
+pub type Point = (i128, i128);
+
+#[derive(Debug, Clone)]
+pub struct RectilinearPolygon {
+    vertices: Vec<Point>,
+}
+
+// As drawn on screen, with rows growing downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+impl RectilinearPolygon {
+    // The vertices in walking order. The loop closes on its own, so repeating the first vertex
+    // at the end is optional. Loops that enclose no area, such as a single edge walked there and
+    // back, are rejected since Pick's theorem does not hold for them.
+    pub fn new(mut vertices: Vec<Point>) -> RectilinearPolygon {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        let polygon = RectilinearPolygon { vertices };
+        for (start, end) in polygon.edges() {
+            if start.0 != end.0 && start.1 != end.1 {
+                panic!("Edge from {start:?} to {end:?} is not horizontal or vertical");
+            }
+        }
+        if polygon.double_area() == 0 {
+            panic!("Polygon {:?} encloses no area", polygon.vertices);
+        }
+        polygon
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    // Every edge as a (start, end) pair, including the one closing the loop
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |k| (self.vertices[k], self.vertices[(k + 1) % n]))
+    }
+
+    // Twice the signed area from the shoelace formula, positive when counter-clockwise
+    pub fn double_signed_area(&self) -> i128 {
+        overflow::sum(
+            self.edges().map(|(start, end)| {
+                overflow::sub(
+                    overflow::mul(start.0, end.1, "shoelace term"),
+                    overflow::mul(start.1, end.0, "shoelace term"),
+                    "shoelace term",
+                )
+            }),
+            "polygon area",
+        )
+    }
+
+    pub fn double_area(&self) -> u128 {
+        self.double_signed_area().unsigned_abs()
+    }
+
+    pub fn perimeter(&self) -> u128 {
+        overflow::sum(
+            self.edges()
+                .map(|(start, end)| start.0.abs_diff(end.0) + start.1.abs_diff(end.1)),
+            "perimeter",
+        )
+    }
+
+    // Lattice points on the edges. Every unit of a horizontal or vertical edge ends on one,
+    // so there are as many as the perimeter is long.
+    pub fn boundary_points(&self) -> u128 {
+        self.perimeter()
+    }
+
+    // Lattice points strictly inside, from Pick's theorem: A = i + b / 2 - 1
+    pub fn interior_points(&self) -> u128 {
+        // Only a loop that crosses or retraces itself has more boundary than this allows
+        let double_interior = overflow::add(self.double_area(), 2, "interior points")
+            .checked_sub(self.boundary_points())
+            .expect("Pick's theorem needs a polygon that does not cross itself");
+        double_interior / 2
+    }
+
+    // Lattice points inside or on the edges, i.e. the unit squares centred on them
+    pub fn lattice_points(&self) -> u128 {
+        overflow::add(
+            self.interior_points(),
+            self.boundary_points(),
+            "lattice points",
+        )
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        if self.double_signed_area() > 0 {
+            Orientation::CounterClockwise
+        } else {
+            Orientation::Clockwise
+        }
+    }
+
+    // Casts a ray along the point's row towards growing columns. Vertical edges cover the rows
+    // from their upper end up to, but excluding, their lower end, so that a ray running along
+    // a horizontal edge counts the crossing once.
+    pub fn contains(&self, point: Point) -> Containment {
+        let (row, column) = point;
+        let mut crossings: u64 = 0;
+        for (start, end) in self.edges() {
+            let rows = (start.0.min(end.0), start.0.max(end.0));
+            let columns = (start.1.min(end.1), start.1.max(end.1));
+            if rows.0 <= row && row <= rows.1 && columns.0 <= column && column <= columns.1 {
+                return Containment::Boundary;
+            }
+            if start.1 == end.1 && start.1 > column && rows.0 <= row && row < rows.1 {
+                crossings += 1;
+            }
+        }
+
+        if crossings % 2 == 1 {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Containment, Orientation, RectilinearPolygon};
+    use test_case::test_case;
+
+    // A 5x5 square with a 2x2 notch cut out of its top right corner:
+    //
+    //   ###..
+    //   #.###
+    //   #...#
+    //   #...#
+    //   #####
+    fn notched_square() -> RectilinearPolygon {
+        RectilinearPolygon::new(vec![(0, 0), (0, 2), (1, 2), (1, 4), (4, 4), (4, 0), (0, 0)])
+    }
+
+    #[test]
+    fn measures_polygon() {
+        let polygon = notched_square();
+
+        assert_eq!(polygon.vertices().len(), 6);
+        assert_eq!(polygon.double_area(), 28);
+        assert_eq!(polygon.perimeter(), 16);
+        assert_eq!(polygon.boundary_points(), 16);
+        assert_eq!(polygon.interior_points(), 7);
+        assert_eq!(polygon.lattice_points(), 23);
+    }
+
+    #[test]
+    fn finds_orientation() {
+        let clockwise = notched_square();
+        let mut vertices = clockwise.vertices().to_vec();
+        vertices.reverse();
+        let counter_clockwise = RectilinearPolygon::new(vertices);
+
+        assert_eq!(clockwise.orientation(), Orientation::Clockwise);
+        assert_eq!(
+            counter_clockwise.orientation(),
+            Orientation::CounterClockwise
+        );
+        assert_eq!(
+            clockwise.double_signed_area(),
+            -counter_clockwise.double_signed_area()
+        );
+    }
+
+    #[test_case((2, 2), Containment::Inside)]
+    #[test_case((1, 1), Containment::Inside)]
+    #[test_case((0, 3), Containment::Outside)]
+    #[test_case((1, 5), Containment::Outside)]
+    #[test_case((-1, 2), Containment::Outside)]
+    #[test_case((1, 3), Containment::Boundary)]
+    #[test_case((4, 4), Containment::Boundary)]
+    fn locates_points(point: (i128, i128), expected: Containment) {
+        let polygon = notched_square();
+
+        assert_eq!(polygon.contains(point), expected);
+    }
+
+    #[test_case(vec![(0, 0)])]
+    #[test_case(vec![(0, 0), (0, 2)])]
+    #[test_case(vec![(0, 0), (0, 2), (0, 1)])]
+    #[test_case(vec![(0, 0), (0, 2), (1, 2), (1, 0), (0, 0), (0, 2), (-1, 2), (-1, 0)])]
+    #[should_panic(expected = "encloses no area")]
+    fn rejects_degenerate_polygons(vertices: Vec<(i128, i128)>) {
+        RectilinearPolygon::new(vertices);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not cross itself")]
+    fn rejects_interior_of_retraced_loops() {
+        let square = [(0, 0), (0, 1), (1, 1), (1, 0)];
+
+        RectilinearPolygon::new([square, square].concat()).interior_points();
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_diagonal_edges() {
+        RectilinearPolygon::new(vec![(0, 0), (0, 2), (2, 0)]);
+    }
+}